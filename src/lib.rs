@@ -1,5 +1,7 @@
 mod server;
 
+use std::time::Duration;
+
 use log::error;
 use server::Server;
 use wasm_bindgen::prelude::*;
@@ -19,10 +21,8 @@ pub fn init_language_server(writer: web_sys::WritableStreamDefaultWriter) -> Ser
     Server::new(move |message| send_message(&writer, message))
 }
 
-async fn read_message(
-    reader: &web_sys::ReadableStreamDefaultReader,
-) -> Result<(String, bool), String> {
-    match JsFuture::from(reader.read()).await {
+fn read_message(result: Result<JsValue, JsValue>) -> Result<(String, bool), String> {
+    match result {
         Ok(js_object) => {
             let value = js_sys::Reflect::get(&js_object, &"value".into())
                 .map_err(|_| "\"value\" property not present in message")?
@@ -38,18 +38,50 @@ async fn read_message(
     }
 }
 
+/// A promise that resolves to `undefined` after the given duration.
+fn timeout(duration: Duration) -> js_sys::Promise {
+    js_sys::Promise::new(&mut |resolve, _reject| match js_sys::Reflect::get(
+        &js_sys::global(),
+        &"setTimeout".into(),
+    )
+    .and_then(|set_timeout| set_timeout.dyn_into::<js_sys::Function>())
+    {
+        Ok(set_timeout) => {
+            let delay = JsValue::from(duration.as_millis() as f64);
+            if set_timeout.call2(&JsValue::NULL, &resolve, &delay).is_err() {
+                error!("Could not schedule timeout");
+            }
+        }
+        Err(_) => error!("\"setTimeout\" is not available"),
+    })
+}
+
 #[wasm_bindgen]
 impl Server {
     pub async fn listen(&mut self, reader: web_sys::ReadableStreamDefaultReader) {
+        let mut pending_read = None;
         loop {
-            match read_message(&reader).await {
-                Ok((value, done)) => {
-                    self.handle_message(value);
-                    if done {
-                        break;
-                    }
+            let read = pending_read.take().unwrap_or_else(|| reader.read());
+            // NOTE: Wait for the next message, but at most until held back diagnostics are due.
+            let next = match self.diagnostics_due_in() {
+                Some(delay) => js_sys::Promise::race(&js_sys::Array::of2(&read, &timeout(delay))),
+                None => read.clone(),
+            };
+            match JsFuture::from(next).await {
+                // NOTE: Only the timeout resolves to `undefined`, the read is still pending.
+                Ok(result) if result.is_undefined() => {
+                    pending_read = Some(read);
+                    self.handle_idle();
                 }
-                Err(e) => error!("{}", e),
+                result => match read_message(result) {
+                    Ok((value, done)) => {
+                        self.handle_message(value);
+                        if done {
+                            break;
+                        }
+                    }
+                    Err(e) => error!("{}", e),
+                },
             }
         }
     }
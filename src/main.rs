@@ -21,7 +21,7 @@ use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use server::{format_raw, Server};
 
 use clap::{Parser, Subcommand};
-use stdio_reader::listen_stdio;
+use stdio_reader::{listen_stdio, StdioEvent};
 
 /// qlue-ls: An SPARQL language server and formatter
 #[derive(Debug, Parser)]
@@ -87,7 +87,13 @@ fn main() {
         Command::Server => {
             // Start server and listen to stdio
             let mut server = Server::new(send_message);
-            listen_stdio(|message| server.handle_message(message));
+            listen_stdio(|event| {
+                match event {
+                    StdioEvent::Message(message) => server.handle_message(message),
+                    StdioEvent::Timeout => server.handle_idle(),
+                }
+                server.diagnostics_due_in()
+            });
         }
         Command::Format {
             path,
@@ -1,8 +1,8 @@
 use streaming_iterator::StreamingIterator;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
-use tree_sitter::{Node, Query, QueryCursor};
+use tree_sitter::{Node, Query, QueryCursor, Tree};
 
 use super::{
     lsp::{
@@ -43,24 +43,13 @@ fn collect_all_unique_captures(
     Ok(capture_set.into_iter().collect())
 }
 
-pub fn get_all_variables(
-    analyis_state: &ServerState,
-    uri: &String,
-) -> Result<Vec<String>, ResponseError> {
-    let (document, tree) = analyis_state.get_state(uri)?;
-    collect_all_unique_captures(tree.root_node(), "(VAR) @variable", &document.text)
-}
-
 pub fn get_kind_at_position(
     analyis_state: &ServerState,
     uri: &String,
     position: &Position,
 ) -> Result<&'static str, ResponseError> {
-    let tree = analyis_state.get_tree(uri)?;
-    let point = position.to_point();
-
-    tree.root_node()
-        .descendant_for_point_range(point, point)
+    let (document, tree) = analyis_state.get_state(uri)?;
+    get_node_at_position(tree, &document.text, position)
         .ok_or(ResponseError::new(
             ErrorCode::InternalError,
             &format!("Could not get kind at position {} of {}", position, uri),
@@ -302,6 +291,932 @@ pub(crate) fn get_undeclared_prefixes(
         .into_iter())
 }
 
+/// Returns the variable name of a `VAR` node, without its leading `?` or `$`.
+///
+/// In SPARQL `?name` and `$name` denote the same variable.
+pub(crate) fn variable_name<'a>(node: &Node, text: &'a str) -> &'a str {
+    &text[node.start_byte() + 1..node.end_byte()]
+}
+
+/// Checks if a `SubSelect` projects the variable `name` to its enclosing scope.
+fn projects_variable(sub_select: Node, name: &str, text: &str) -> bool {
+    let mut cursor = sub_select.walk();
+    let Some(select_clause) = sub_select
+        .children(&mut cursor)
+        .find(|child| child.kind() == "SelectClause")
+    else {
+        return false;
+    };
+    let mut cursor = select_clause.walk();
+    let projected = select_clause
+        .children(&mut cursor)
+        .any(|child| match child.kind() {
+            "*" => true,
+            "VAR" => variable_name(&child, text) == name,
+            "assignment" => child
+                .child_by_field_name("bound_variable")
+                .is_some_and(|variable| variable_name(&variable, text) == name),
+            _ => false,
+        });
+    projected
+}
+
+/// Checks if a `VAR` node binds its variable, `ancestors` are the ancestors of the node,
+/// the parent last.
+///
+/// Variables get bound by triple patterns, `BIND (... AS ?var)`, `VALUES`,
+/// `GRAPH ?var` or the projection of a subquery.
+fn is_variable_binding(variable: &Node, ancestors: &[Node]) -> bool {
+    let Some((parent, ancestors)) = ancestors.split_last() else {
+        return false;
+    };
+    match parent.kind() {
+        "assignment" => parent.child_by_field_name("bound_variable") == Some(*variable),
+        "DataBlock" | "GraphGraphPattern" => true,
+        "SelectClause" => ancestors
+            .last()
+            .is_some_and(|grandparent| grandparent.kind() == "SubSelect"),
+        "TriplesSameSubjectPath"
+        | "TriplesSameSubject"
+        | "PropertyListPathNotEmpty"
+        | "PropertyListNotEmpty"
+        | "ObjectList"
+        | "collection" => {
+            // NOTE: Triples in templates do not bind variables, they use them.
+            // Triples of `EXISTS` groups only test the solutions, they bind nothing either.
+            !ancestors.iter().any(|ancestor| {
+                matches!(
+                    ancestor.kind(),
+                    "ConstructTemplate"
+                        | "DeleteClause"
+                        | "InsertClause"
+                        | "ExistsFunc"
+                        | "NotExistsFunc"
+                )
+            })
+        }
+        _ => false,
+    }
+}
+
+/// Checks if the scope `outer` encloses the scope `inner`.
+///
+/// Scopes (the root node and `SubSelect`s) are nested and never share their range, so this
+/// compares ranges instead of walking up the parse-tree.
+fn encloses(outer: Node, inner: Node) -> bool {
+    outer.start_byte() <= inner.start_byte() && inner.end_byte() <= outer.end_byte()
+}
+
+fn contains_byte(node: &Node, byte: usize) -> bool {
+    node.start_byte() <= byte && byte < node.end_byte()
+}
+
+/// A block that hides the variables inside of it from parts of its scope.
+#[derive(Clone, Copy)]
+enum HidingBlock<'a> {
+    /// Variables of a `MINUS` block are only visible inside of it.
+    Minus(Node<'a>),
+    /// Variables of an `EXISTS` or `NOT EXISTS` group are only visible inside of it.
+    Exists(Node<'a>),
+    /// Variables of a `UNION` branch are not visible in the other branches.
+    Branch { union: Node<'a>, branch: Node<'a> },
+}
+
+impl HidingBlock<'_> {
+    /// The node that opens the block.
+    fn node(&self) -> &Node<'_> {
+        match self {
+            HidingBlock::Minus(minus) => minus,
+            HidingBlock::Exists(exists) => exists,
+            HidingBlock::Branch { union, .. } => union,
+        }
+    }
+
+    fn hides_at(&self, byte: usize) -> bool {
+        match self {
+            HidingBlock::Minus(minus) => !contains_byte(minus, byte),
+            HidingBlock::Exists(exists) => !contains_byte(exists, byte),
+            HidingBlock::Branch { union, branch } => {
+                contains_byte(union, byte) && !contains_byte(branch, byte)
+            }
+        }
+    }
+}
+
+/// Collects the `VAR` nodes below `node`. `ancestors` are the ancestors of `node` and `blocks`
+/// the hiding blocks `node` is in, both outermost first.
+///
+/// NOTE: `Node::parent` searches down from the root, the ancestors are tracked instead.
+fn collect_scoped_variables<'a>(
+    node: Node<'a>,
+    text: &'a str,
+    ancestors: &mut Vec<Node<'a>>,
+    blocks: &mut Vec<HidingBlock<'a>>,
+    variables: &mut Vec<ScopedVariable<'a>>,
+) {
+    ancestors.push(node);
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.children(&mut cursor).collect();
+    for child in children {
+        let block = match (node.kind(), child.kind()) {
+            (_, "MinusGraphPattern") => Some(HidingBlock::Minus(child)),
+            (_, "ExistsFunc" | "NotExistsFunc") => Some(HidingBlock::Exists(child)),
+            ("GroupOrUnionGraphPattern", _) => Some(HidingBlock::Branch {
+                union: node,
+                branch: child,
+            }),
+            _ => None,
+        };
+        let has_block = block.is_some();
+        blocks.extend(block);
+        match child.kind() {
+            // NOTE: Variables the parser expected but that are missing are not variables.
+            "VAR" if child.start_byte() == child.end_byte() => {}
+            "VAR" => {
+                let name = variable_name(&child, text);
+                // NOTE: The enclosing scopes, innermost first.
+                let mut scopes = ancestors
+                    .iter()
+                    .rev()
+                    .filter(|ancestor| ancestor.kind() == "SubSelect")
+                    .chain(ancestors.first())
+                    .copied();
+                let scope = scopes.next().unwrap_or(node);
+                let mut resolved_scope = scope;
+                while resolved_scope.kind() == "SubSelect"
+                    && projects_variable(resolved_scope, name, text)
+                {
+                    match scopes.next() {
+                        Some(outer) => resolved_scope = outer,
+                        None => break,
+                    }
+                }
+                let is_binding = is_variable_binding(&child, ancestors);
+                let bind_group = match ancestors.as_slice() {
+                    [.., group, bind, assignment]
+                        if is_binding
+                            && bind.kind() == "Bind"
+                            && assignment.kind() == "assignment" =>
+                    {
+                        Some(*group)
+                    }
+                    _ => None,
+                };
+                let in_solution_clause = ancestors
+                    .iter()
+                    .rev()
+                    .take_while(|ancestor| **ancestor != scope)
+                    .any(|ancestor| {
+                        matches!(
+                            ancestor.kind(),
+                            "SelectClause" | "GroupClause" | "OrderClause"
+                        )
+                    });
+                variables.push(ScopedVariable {
+                    node: child,
+                    parent: node,
+                    name,
+                    scope,
+                    resolved_scope,
+                    is_binding,
+                    bind_group,
+                    in_solution_clause,
+                    hiding_blocks: blocks.clone(),
+                });
+            }
+            _ => collect_scoped_variables(child, text, ancestors, blocks, variables),
+        }
+        if has_block {
+            blocks.pop();
+        }
+    }
+    ancestors.pop();
+}
+
+/// A `VAR` node and the scopes it belongs to.
+struct ScopedVariable<'a> {
+    node: Node<'a>,
+    parent: Node<'a>,
+    name: &'a str,
+    /// The innermost scope the variable occurs in.
+    scope: Node<'a>,
+    /// The outermost scope the variable is part of: subqueries that project the variable share
+    /// it with their enclosing scope.
+    resolved_scope: Node<'a>,
+    is_binding: bool,
+    /// The group of the `BIND` that binds the variable, if it is bound by a `BIND`.
+    bind_group: Option<Node<'a>>,
+    /// Whether the variable occurs in the `SELECT`, `GROUP BY` or `ORDER BY` clause of its scope.
+    in_solution_clause: bool,
+    /// The hiding blocks the variable is in, outermost first.
+    hiding_blocks: Vec<HidingBlock<'a>>,
+}
+
+impl ScopedVariable<'_> {
+    /// Checks if the block structure hides the variable at the byte index `byte`, that is if the
+    /// variable is in a `MINUS` block or `EXISTS` group that does not contain `byte`, or in
+    /// another branch of a `UNION`. Only blocks inside of `scope` are considered.
+    fn is_hidden_at(&self, scope: Node, byte: usize) -> bool {
+        // NOTE: The blocks and `scope` enclose the variable, so a block is inside of `scope`
+        // if its range is.
+        self.hiding_blocks.iter().any(|block| {
+            let node = block.node();
+            scope.start_byte() <= node.start_byte()
+                && node.end_byte() <= scope.end_byte()
+                && *node != scope
+                && block.hides_at(byte)
+        })
+    }
+}
+
+/// A `BIND` that binds a variable.
+struct BindScope<'a> {
+    /// The start of the bound variable.
+    start: usize,
+    /// The group the `BIND` is in.
+    group: Node<'a>,
+}
+
+/// The scopes of the variables of a document.
+///
+/// The document and each `SubSelect` open a scope. A subquery shares a variable with its
+/// enclosing scope only if it projects the variable, otherwise the variables are different ones,
+/// even with the same name.
+///
+/// Within a scope:
+/// - variables of `OPTIONAL` blocks and `UNION` branches are visible in the enclosing group,
+///   but not in the other branches of the `UNION`,
+/// - variables of a `MINUS` block are only visible inside of it,
+/// - variables of an `EXISTS` or `NOT EXISTS` group are only visible inside of it,
+/// - variables introduced by `BIND` are visible after the `BIND` and in the `FILTER`s of its
+///   group, since a `FILTER` constrains the whole group,
+/// - `VALUES` bind their variables for the whole group.
+///
+/// The model is built in one pass over the parse-tree, lookups do not scan the document again.
+pub(crate) struct ScopeModel<'a> {
+    root: Node<'a>,
+    text: &'a str,
+    /// The variables in document order.
+    variables: Vec<ScopedVariable<'a>>,
+    /// The index of each `VAR` node (by id) in `variables`.
+    indices: HashMap<usize, usize>,
+    /// The indices of the occurrences of each variable, by name and (resolved) scope id.
+    occurrences: HashMap<(&'a str, usize), Vec<usize>>,
+    /// The `BIND`s of each variable name.
+    binds: HashMap<&'a str, Vec<BindScope<'a>>>,
+}
+
+impl<'a> ScopeModel<'a> {
+    pub(crate) fn new(root: Node<'a>, text: &'a str) -> Self {
+        let mut variables = vec![];
+        collect_scoped_variables(root, text, &mut vec![], &mut vec![], &mut variables);
+        let mut indices = HashMap::new();
+        let mut occurrences: HashMap<(&str, usize), Vec<usize>> = HashMap::new();
+        let mut binds: HashMap<&str, Vec<BindScope>> = HashMap::new();
+        for (index, variable) in variables.iter().enumerate() {
+            indices.insert(variable.node.id(), index);
+            occurrences
+                .entry((variable.name, variable.resolved_scope.id()))
+                .or_default()
+                .push(index);
+            if let Some(group) = variable.bind_group {
+                binds.entry(variable.name).or_default().push(BindScope {
+                    start: variable.node.start_byte(),
+                    group,
+                });
+            }
+        }
+        Self {
+            root,
+            text,
+            variables,
+            indices,
+            occurrences,
+            binds,
+        }
+    }
+
+    fn get(&self, variable: &Node) -> Option<&ScopedVariable<'a>> {
+        self.indices
+            .get(&variable.id())
+            .map(|index| &self.variables[*index])
+    }
+
+    /// Returns all occurrences of the variable denoted by the `VAR` node `variable`,
+    /// in document order.
+    pub(crate) fn occurrences(&self, variable: &Node) -> Vec<Node<'a>> {
+        let Some(target) = self.get(variable) else {
+            return vec![];
+        };
+        self.occurrences
+            .get(&(target.name, target.resolved_scope.id()))
+            .map(|indices| {
+                indices
+                    .iter()
+                    .map(|index| self.variables[*index].node)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the occurrences of the variable denoted by `variable` that bind it.
+    pub(crate) fn bindings(&self, variable: &Node) -> Vec<Node<'a>> {
+        self.occurrences(variable)
+            .into_iter()
+            .filter(|occurrence| self.is_binding(occurrence))
+            .collect()
+    }
+
+    /// Checks if another variable named `name` occurs in a scope of the variable denoted by
+    /// `variable`, renaming the variable to `name` would merge the two.
+    pub(crate) fn is_name_taken(&self, variable: &Node, name: &str) -> bool {
+        let Some(target) = self.get(variable) else {
+            return false;
+        };
+        if target.name == name {
+            return false;
+        }
+        let scopes: Vec<Node> = self
+            .occurrences(variable)
+            .iter()
+            .filter_map(|occurrence| self.get(occurrence))
+            .map(|occurrence| occurrence.scope)
+            .collect();
+        self.variables.iter().any(|other| {
+            other.name == name
+                && (other.resolved_scope == target.resolved_scope || scopes.contains(&other.scope))
+        })
+    }
+
+    /// Checks if a `VAR` node binds its variable.
+    ///
+    /// Variables get bound by triple patterns, `BIND (... AS ?var)`, `VALUES`,
+    /// `GRAPH ?var` or the projection of a subquery.
+    pub(crate) fn is_binding(&self, variable: &Node) -> bool {
+        self.get(variable)
+            .is_some_and(|scoped_variable| scoped_variable.is_binding)
+    }
+
+    /// Checks if the variable denoted by the `VAR` node `variable` is part of the results of
+    /// the query.
+    ///
+    /// Returns `None` if the document is not a `SELECT` query, other query forms and updates do
+    /// not project variables.
+    pub(crate) fn is_projected(&self, variable: &Node) -> Option<bool> {
+        let target = self.get(variable)?;
+        if target.resolved_scope.kind() == "SubSelect" {
+            return Some(false);
+        }
+        let mut cursor = self.root.walk();
+        let select_query = self
+            .root
+            .children(&mut cursor)
+            .find(|child| child.kind() == "SelectQuery")?;
+        Some(projects_variable(select_query, target.name, self.text))
+    }
+
+    /// Returns the variables (as written, e.g. `?x`) that are visible at the byte index `byte`,
+    /// sorted and without duplicates.
+    pub(crate) fn visible_at(&self, byte: usize) -> Vec<&'a str> {
+        let Some(node) = self.root.descendant_for_byte_range(byte, byte) else {
+            return vec![];
+        };
+        let mut ancestors = vec![node];
+        while let Some(parent) = ancestors.last().and_then(|node| node.parent()) {
+            ancestors.push(parent);
+        }
+        let scope = ancestors
+            .iter()
+            .find(|ancestor| ancestor.kind() == "SubSelect")
+            .copied()
+            .unwrap_or(self.root);
+        let visible: Vec<&ScopedVariable<'a>> = self
+            .variables
+            .iter()
+            .filter(|variable| {
+                encloses(scope, variable.scope)
+                    && encloses(variable.resolved_scope, scope)
+                    && !variable.is_hidden_at(scope, byte)
+            })
+            .collect();
+        // NOTE: A `BIND` hides its variable in the group before it, besides `FILTER`s.
+        let hidden_by_bind = |name: &str| {
+            self.binds.get(name).is_some_and(|binds| {
+                binds.iter().any(|bind| {
+                    byte < bind.start
+                        && contains_byte(&bind.group, byte)
+                        && !ancestors
+                            .iter()
+                            .take_while(|ancestor| **ancestor != bind.group)
+                            .any(|ancestor| ancestor.kind() == "Filter")
+                })
+            })
+        };
+        let mut names: Vec<&'a str> = visible
+            .into_iter()
+            .filter(|variable| !hidden_by_bind(variable.name))
+            .filter_map(|variable| variable.node.utf8_text(self.text.as_bytes()).ok())
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Returns the bindings of `scope` that are part of its solutions, at the projection or
+    /// solution modifier at the byte index `byte`.
+    ///
+    /// Bindings of nested subqueries count if the subquery projects them,
+    /// bindings inside of `MINUS` blocks or `EXISTS` groups never do.
+    fn solution_bindings(
+        &self,
+        scope: Node<'a>,
+        byte: usize,
+    ) -> impl Iterator<Item = &ScopedVariable<'a>> {
+        self.variables.iter().filter(move |variable| {
+            variable.is_binding
+                && variable.parent.kind() != "SelectClause"
+                && encloses(scope, variable.scope)
+                && encloses(variable.resolved_scope, scope)
+                && !variable.is_hidden_at(scope, byte)
+        })
+    }
+
+    /// Returns the variables of `SELECT`, `GROUP BY` and `ORDER BY` clauses that are never
+    /// bound by the `WHERE` clause of their query, e.g. `?nmae` in
+    /// `SELECT ?nmae WHERE { ?s :name ?name }`.
+    ///
+    /// Each variable comes with the variables (as written) that are bound instead,
+    /// sorted and without duplicates.
+    pub(crate) fn unbound_projections(&self) -> Vec<(Node<'a>, Vec<&'a str>)> {
+        // NOTE: The clauses are outside of the `WHERE` clause, so all clauses of a scope see the
+        // same bindings. They are computed once per scope (by id).
+        let mut bound_in_scope: HashMap<usize, Vec<&'a str>> = HashMap::new();
+        let mut unbound = vec![];
+        // NOTE: `(... AS ?var)` binds the variable, the projection of a subquery does not.
+        for variable in self.variables.iter().filter(|variable| {
+            variable.in_solution_clause
+                && (!variable.is_binding || variable.parent.kind() == "SelectClause")
+        }) {
+            let bound = bound_in_scope
+                .entry(variable.scope.id())
+                .or_insert_with(|| {
+                    let mut bound: Vec<&'a str> = self
+                        .solution_bindings(variable.scope, variable.node.start_byte())
+                        .filter_map(|binding| binding.node.utf8_text(self.text.as_bytes()).ok())
+                        .collect();
+                    bound.sort();
+                    bound.dedup();
+                    bound
+                });
+            if !bound.iter().any(|binding| &binding[1..] == variable.name) {
+                unbound.push((variable.node, bound.clone()));
+            }
+        }
+        unbound
+    }
+}
+
+/// Returns the variables of `SELECT`, `GROUP BY` and `ORDER BY` clauses that are never bound,
+/// their range and the bound variable with the most similar name, if there is a similar one.
+pub(crate) fn get_unbound_projections(
+    server_state: &ServerState,
+    document_uri: &str,
+) -> Result<Vec<(String, Range, Option<String>)>, ResponseError> {
+    let (document, tree) = server_state.get_state(document_uri)?;
+    let scopes = ScopeModel::new(tree.root_node(), &document.text);
+    Ok(scopes
+        .unbound_projections()
+        .into_iter()
+        .map(|(node, bound)| {
+            let written = node.utf8_text(document.text.as_bytes()).unwrap_or_default();
+            let name = variable_name(&node, &document.text);
+            // NOTE: Names further away than half their length are not typos of each other.
+            let closest = bound
+                .into_iter()
+                .map(|candidate| {
+                    (
+                        strsim::damerau_levenshtein(name, &candidate[1..]),
+                        candidate,
+                    )
+                })
+                .filter(|(distance, _)| *distance <= name.chars().count() / 2)
+                .min_by_key(|(distance, _)| *distance)
+                .map(|(_, candidate)| format!("{}{}", &written[..1], &candidate[1..]));
+            (written.to_string(), Range::from_node(&node), closest)
+        })
+        .collect())
+}
+
+/// Returns the node at `position`, its column counts UTF-16 code units like all LSP positions.
+pub(crate) fn get_node_at_position<'a>(
+    tree: &'a Tree,
+    text: &str,
+    position: &Position,
+) -> Option<Node<'a>> {
+    let byte = position.to_byte_index(text)?;
+    tree.root_node().descendant_for_byte_range(byte, byte)
+}
+
+/// Returns the `PNAME_NS` node of a prefixed name, given any of its parts.
+fn get_namespace_node(node: Node) -> Option<Node> {
+    match node.kind() {
+        "PNAME_NS" => Some(node),
+        "PN_PREFIX" | ":" => node.parent().filter(|parent| parent.kind() == "PNAME_NS"),
+        "PN_LOCAL" => node
+            .parent()
+            .filter(|parent| parent.kind() == "PrefixedName")
+            .and_then(|prefixed_name| prefixed_name.child(0)),
+        _ => None,
+    }
+}
+
+/// Returns the `PrefixDecl` node that declares the namespace `pname_ns` (e.g. `wdt:`).
+fn find_prefix_declaration<'a>(root: Node<'a>, pname_ns: &str, text: &str) -> Option<Node<'a>> {
+    let mut cursor = root.walk();
+    let prologue = root
+        .children(&mut cursor)
+        .find(|child| child.kind() == "Prologue")?;
+    let mut cursor = prologue.walk();
+    let declaration = prologue.children(&mut cursor).find(|child| {
+        child.kind() == "PrefixDecl"
+            && child
+                .child(1)
+                .and_then(|namespace| namespace.utf8_text(text.as_bytes()).ok())
+                == Some(pname_ns)
+    });
+    declaration
+}
+
+/// Computes the location of the definition of the symbol at `position`.
+///
+/// For prefixed names this is the `PrefixDecl` of the prefix, for variables it is the
+/// first place the variable gets bound.
+pub(crate) fn get_definition(
+    server_state: &ServerState,
+    document_uri: &str,
+    position: &Position,
+) -> Result<Option<Range>, ResponseError> {
+    let (document, tree) = server_state.get_state(document_uri)?;
+    let text = &document.text;
+    let Some(node) = get_node_at_position(tree, text, position) else {
+        return Ok(None);
+    };
+    let mut range = match node.kind() {
+        "VAR" => ScopeModel::new(tree.root_node(), text)
+            .bindings(&node)
+            .first()
+            .map(Range::from_node),
+        "PN_PREFIX" | ":" | "PNAME_NS" | "PN_LOCAL" => get_namespace_node(node)
+            .and_then(|namespace| namespace.utf8_text(text.as_bytes()).ok())
+            .and_then(|namespace| find_prefix_declaration(tree.root_node(), namespace, text))
+            .and_then(|declaration| declaration.child(1))
+            .map(|namespace| Range::from_node(&namespace)),
+        _ => None,
+    };
+    if let Some(range) = range.as_mut() {
+        range.translate_to_utf16_encoding(text)?;
+    }
+    Ok(range)
+}
+
+/// An occurrence of a variable, prefix or IRI in a document.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Occurrence {
+    pub(crate) range: Range,
+    /// `true` if this occurrence declares the prefix or binds the variable.
+    pub(crate) is_declaration: bool,
+}
+
+fn collect_all_captured_nodes<'a>(
+    node: Node<'a>,
+    query_str: &str,
+    text: &str,
+) -> Result<Vec<Node<'a>>, ResponseError> {
+    let query = build_query(query_str)?;
+    let mut query_cursor = QueryCursor::new();
+    let mut captures = query_cursor.captures(&query, node, text.as_bytes());
+    let mut nodes = Vec::new();
+    while let Some((mat, capture_index)) = captures.next() {
+        nodes.push(mat.captures[*capture_index].node);
+    }
+    Ok(nodes)
+}
+
+/// Maps each declared namespace (e.g. `wdt:`) to its IRI (without the angle brackets).
+pub(crate) fn get_prefix_map(root: Node, text: &str) -> HashMap<String, String> {
+    let mut cursor = root.walk();
+    let Some(prologue) = root
+        .children(&mut cursor)
+        .find(|child| child.kind() == "Prologue")
+    else {
+        return HashMap::new();
+    };
+    let mut cursor = prologue.walk();
+    let prefix_map = prologue
+        .children(&mut cursor)
+        .filter(|child| child.kind() == "PrefixDecl")
+        .filter_map(|declaration| {
+            let namespace = declaration.child(1)?.utf8_text(text.as_bytes()).ok()?;
+            let iri = declaration.child(2)?.utf8_text(text.as_bytes()).ok()?;
+            Some((namespace.to_string(), strip_angle_brackets(iri).to_string()))
+        })
+        .collect();
+    prefix_map
+}
+
+pub(crate) fn strip_angle_brackets(iri: &str) -> &str {
+    iri.strip_prefix('<')
+        .and_then(|iri| iri.strip_suffix('>'))
+        .unwrap_or(iri)
+}
+
+/// Checks if `local_name` can be written in a prefixed name without escaping.
+pub(crate) fn is_simple_local_name(local_name: &str) -> bool {
+    local_name
+        .chars()
+        .all(|char| char.is_alphanumeric() || matches!(char, '_' | '-' | '.'))
+}
+
+/// Compacts `iri` with the longest declared namespace it starts with.
+pub(crate) fn compact_with_declared_prefix(
+    prefix_map: &HashMap<String, String>,
+    iri: &str,
+) -> Option<String> {
+    prefix_map
+        .iter()
+        .filter_map(|(namespace, uri_prefix)| {
+            let local_name = iri.strip_prefix(uri_prefix.as_str())?;
+            is_simple_local_name(local_name)
+                .then_some((uri_prefix.len(), format!("{}{}", namespace, local_name)))
+        })
+        .max()
+        .map(|(_, curie)| curie)
+}
+
+/// Resolves an `IRIREF` or `PrefixedName` node to the full IRI it denotes.
+/// Returns `None` if the prefix of a prefixed name is not declared.
+pub(crate) fn resolve_iri(
+    node: &Node,
+    prefix_map: &HashMap<String, String>,
+    text: &str,
+) -> Option<String> {
+    match node.kind() {
+        "IRIREF" => Some(strip_angle_brackets(node.utf8_text(text.as_bytes()).ok()?).to_string()),
+        "PrefixedName" => {
+            let namespace = node.child(0)?.utf8_text(text.as_bytes()).ok()?;
+            let local_name = node
+                .child(1)
+                .and_then(|local_name| local_name.utf8_text(text.as_bytes()).ok())
+                .unwrap_or("")
+                .replace('\\', "");
+            Some(format!("{}{}", prefix_map.get(namespace)?, local_name))
+        }
+        _ => None,
+    }
+}
+
+/// Collects the full IRI and range of every `IRIREF` and `PrefixedName` outside of
+/// `PREFIX` and `BASE` declarations.
+/// Prefixed names with an undeclared prefix are skipped.
+pub(crate) fn get_resolved_iris(
+    server_state: &ServerState,
+    document_uri: &str,
+) -> Result<Vec<(String, Range)>, ResponseError> {
+    let (document, tree) = server_state.get_state(document_uri)?;
+    let text = &document.text;
+    let root = tree.root_node();
+    let prefix_map = get_prefix_map(root, text);
+    Ok(
+        collect_all_captured_nodes(root, "[(IRIREF) (PrefixedName)] @iri", text)?
+            .into_iter()
+            .filter(|node| {
+                !node
+                    .parent()
+                    .is_some_and(|parent| matches!(parent.kind(), "PrefixDecl" | "BaseDecl"))
+            })
+            .filter_map(|node| {
+                resolve_iri(&node, &prefix_map, text).map(|iri| (iri, Range::from_node(&node)))
+            })
+            .collect(),
+    )
+}
+
+/// Checks if renaming the variable at `position` to `name` would merge it with another variable
+/// of the same scope.
+pub(crate) fn variable_name_is_taken(
+    server_state: &ServerState,
+    document_uri: &str,
+    position: &Position,
+    name: &str,
+) -> Result<bool, ResponseError> {
+    let (document, tree) = server_state.get_state(document_uri)?;
+    Ok(get_node_at_position(tree, &document.text, position)
+        .filter(|node| node.kind() == "VAR")
+        .is_some_and(|node| {
+            ScopeModel::new(tree.root_node(), &document.text).is_name_taken(&node, name)
+        }))
+}
+
+/// Collects all occurrences of the variable, prefix or IRI at `position`, in document order.
+///
+/// IRIs are compared after resolving prefixed names through the declared prefixes,
+/// so `<http://www.wikidata.org/prop/direct/P31>` and `wdt:P31` are the same IRI.
+/// Variables respect the scopes of subqueries.
+pub(crate) fn get_occurrences(
+    server_state: &ServerState,
+    document_uri: &str,
+    position: &Position,
+) -> Result<Vec<Occurrence>, ResponseError> {
+    let (document, tree) = server_state.get_state(document_uri)?;
+    let text = &document.text;
+    let root = tree.root_node();
+    let Some(node) = get_node_at_position(tree, text, position) else {
+        return Ok(vec![]);
+    };
+    let mut occurrences = match node.kind() {
+        "VAR" => {
+            let scopes = ScopeModel::new(root, text);
+            scopes
+                .occurrences(&node)
+                .into_iter()
+                .map(|variable| Occurrence {
+                    range: Range::from_node(&variable),
+                    is_declaration: scopes.is_binding(&variable),
+                })
+                .collect()
+        }
+        "PN_PREFIX" | ":" | "PNAME_NS" => {
+            let Some(namespace) = get_namespace_node(node)
+                .and_then(|namespace| namespace.utf8_text(text.as_bytes()).ok())
+            else {
+                return Ok(vec![]);
+            };
+            collect_all_captured_nodes(root, "(PNAME_NS) @namespace", text)?
+                .into_iter()
+                .filter(|node| node.utf8_text(text.as_bytes()).ok() == Some(namespace))
+                .map(|node| Occurrence {
+                    range: Range::from_node(&node),
+                    is_declaration: node
+                        .parent()
+                        .is_some_and(|parent| parent.kind() == "PrefixDecl"),
+                })
+                .collect()
+        }
+        "IRIREF" | "PN_LOCAL" => {
+            let prefix_map = get_prefix_map(root, text);
+            let iri_node = match node.kind() {
+                "PN_LOCAL" => node.parent().unwrap_or(node),
+                _ => node,
+            };
+            let Some(iri) = resolve_iri(&iri_node, &prefix_map, text) else {
+                return Ok(vec![]);
+            };
+            collect_all_captured_nodes(root, "[(IRIREF) (PrefixedName)] @iri", text)?
+                .into_iter()
+                .filter(|node| {
+                    !node
+                        .parent()
+                        .is_some_and(|parent| matches!(parent.kind(), "PrefixDecl" | "BaseDecl"))
+                })
+                .filter(|node| resolve_iri(node, &prefix_map, text).as_ref() == Some(&iri))
+                .map(|node| Occurrence {
+                    range: Range::from_node(&node),
+                    is_declaration: false,
+                })
+                .collect()
+        }
+        _ => vec![],
+    };
+    // NOTE: LSP counts the characters of a line in UTF-16 code units, tree-sitter in bytes.
+    for occurrence in occurrences.iter_mut() {
+        occurrence.range.translate_to_utf16_encoding(text)?;
+    }
+    Ok(occurrences)
+}
+
+/// Collects all syntax errors of a document.
+///
+/// Tree-sitter recovers from syntax errors by inserting `MISSING` nodes (zero width tokens the
+/// parser expected) or by wrapping the tokens it could not place into `ERROR` nodes.
+/// This function walks the parse-tree and turns both kinds of nodes into a human readable
+/// message and the range the message applies to.
+///
+/// Nodes below an `ERROR` node are not inspected further, since they are covered by the
+/// message of the enclosing `ERROR` node.
+pub(crate) fn get_syntax_errors(
+    server_state: &ServerState,
+    document_uri: &str,
+) -> Result<Vec<(String, Range)>, ResponseError> {
+    let (document, tree) = server_state.get_state(document_uri)?;
+    let root = tree.root_node();
+    if !root.has_error() {
+        return Ok(vec![]);
+    }
+    Ok(collect_syntax_errors(root, &document.text))
+}
+
+fn collect_syntax_errors(node: Node, text: &String) -> Vec<(String, Range)> {
+    if node.is_error() {
+        return error_node_messages(&node, text);
+    }
+    if !node.has_error() {
+        return vec![];
+    }
+    let mut cursor = node.walk();
+    node.children(&mut cursor)
+        .flat_map(|child| match child.is_missing() {
+            // NOTE: The parent is passed down explicitly, since `Node::parent` is not
+            // reliable for zero width nodes.
+            true => vec![(
+                missing_node_message(&child, &node),
+                Range::from_node(&child),
+            )],
+            false => collect_syntax_errors(child, text),
+        })
+        .collect()
+}
+
+fn missing_node_message(node: &Node, parent: &Node) -> String {
+    match node.kind() {
+        "}" | ")" | "]" => format!("expected `{}` to close {}", node.kind(), parent.kind()),
+        kind if node.is_named() => format!("expected {} in {}", kind, parent.kind()),
+        kind => format!("expected `{}` in {}", kind, parent.kind()),
+    }
+}
+
+fn error_node_messages(node: &Node, text: &String) -> Vec<(String, Range)> {
+    // NOTE: Brackets that are opened, but never closed, within the erroneous region
+    // are the most common cause for large ERROR nodes.
+    let mut cursor = node.walk();
+    let mut open_brackets: Vec<Node> = vec![];
+    for child in node.children(&mut cursor) {
+        match (child.kind(), open_brackets.last().map(|open| open.kind())) {
+            ("{", _) | ("(", _) | ("[", _) => open_brackets.push(child),
+            ("}", Some("{")) | (")", Some("(")) | ("]", Some("[")) => {
+                open_brackets.pop();
+            }
+            _ => {}
+        }
+    }
+    if !open_brackets.is_empty() {
+        let end = Position::from_point(node.end_position());
+        return open_brackets
+            .iter()
+            .rev()
+            .map(|open_bracket| {
+                (
+                    format!(
+                        "expected `{}` to close {}",
+                        closing_bracket(open_bracket.kind()),
+                        bracket_context(open_bracket)
+                    ),
+                    Range { start: end, end },
+                )
+            })
+            .collect();
+    }
+    let message = match node.child_count() {
+        1 => format!(
+            "unexpected `{}`",
+            node.utf8_text(text.as_bytes()).unwrap_or_default()
+        ),
+        _ => format!(
+            "could not parse `{}`",
+            shorten_snippet(node.utf8_text(text.as_bytes()).unwrap_or_default())
+        ),
+    };
+    vec![(message, Range::from_node(node))]
+}
+
+fn closing_bracket(open_bracket: &str) -> &'static str {
+    match open_bracket {
+        "{" => "}",
+        "(" => ")",
+        _ => "]",
+    }
+}
+
+/// Guesses the grammar construct an opening bracket belongs to, based on the token in front of it.
+fn bracket_context(open_bracket: &Node) -> &'static str {
+    let previous_kind = open_bracket.prev_sibling().map(|sibling| sibling.kind());
+    match (open_bracket.kind(), previous_kind) {
+        ("{", Some("DATA")) => "QuadData",
+        ("{", Some("CONSTRUCT")) => "ConstructTemplate",
+        ("{", _) => "GroupGraphPattern",
+        ("(", Some("BIND")) => "assignment",
+        ("(", _) => "BrackettedExpression",
+        _ => "BlankNodePropertyList",
+    }
+}
+
+pub(crate) fn shorten_snippet(snippet: &str) -> String {
+    let single_line = snippet.split_whitespace().collect::<Vec<&str>>().join(" ");
+    match single_line.char_indices().nth(40) {
+        Some((idx, _char)) => format!("{}...", &single_line[..idx]),
+        None => single_line,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use indoc::indoc;
@@ -310,9 +1225,10 @@ mod tests {
 
     use crate::server::{
         anaysis::{
-            get_declared_prefixes, get_undeclared_prefixes, get_unused_prefixes, get_used_prefixes,
+            get_declared_prefixes, get_definition, get_occurrences, get_syntax_errors,
+            get_undeclared_prefixes, get_unused_prefixes, get_used_prefixes, ScopeModel,
         },
-        lsp::textdocument::TextDocumentItem,
+        lsp::textdocument::{Position, Range, TextDocumentItem},
         state::ServerState,
     };
 
@@ -384,4 +1300,292 @@ mod tests {
             .collect();
         assert_eq!(declared_namesapces, vec!["wdt", "wdt"]);
     }
+
+    #[test]
+    fn syntax_errors_none() {
+        let state = setup_state(indoc!("SELECT * WHERE { ?s ?p ?o }"));
+        assert_eq!(get_syntax_errors(&state, "uri").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn syntax_errors_missing_bracket() {
+        let state = setup_state(indoc!(
+            "SELECT * WHERE {
+               ?s ?p ?o
+             "
+        ));
+        assert_eq!(
+            get_syntax_errors(&state, "uri").unwrap(),
+            vec![(
+                "expected `}` to close GroupGraphPattern".to_string(),
+                Range::new(1, 8, 1, 8)
+            )]
+        );
+    }
+
+    #[test]
+    fn syntax_errors_unexpected_token() {
+        let state = setup_state(indoc!("SELECT * WHERE { ?s ?p ?o ) }"));
+        assert_eq!(
+            get_syntax_errors(&state, "uri").unwrap(),
+            vec![("unexpected `)`".to_string(), Range::new(0, 26, 0, 27))]
+        );
+    }
+
+    #[test]
+    fn syntax_errors_unclosed_brackets() {
+        let state = setup_state(indoc!("SELECT * WHERE { ?s ?p ?o . FILTER(?s > 1"));
+        assert_eq!(
+            get_syntax_errors(&state, "uri").unwrap(),
+            vec![
+                (
+                    "expected `)` to close BrackettedExpression".to_string(),
+                    Range::new(0, 41, 0, 41)
+                ),
+                (
+                    "expected `}` to close GroupGraphPattern".to_string(),
+                    Range::new(0, 41, 0, 41)
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn definition_of_prefix() {
+        let state = setup_state(indoc!(
+            "PREFIX wdt: <iri>
+             PREFIX : <iri>
+             SELECT * WHERE { ?s wdt:P31 ?o . ?s :p ?o }"
+        ));
+        assert_eq!(
+            get_definition(&state, "uri", &Position::new(2, 25)).unwrap(),
+            Some(Range::new(0, 7, 0, 11))
+        );
+        assert_eq!(
+            get_definition(&state, "uri", &Position::new(2, 21)).unwrap(),
+            Some(Range::new(0, 7, 0, 11))
+        );
+        assert_eq!(
+            get_definition(&state, "uri", &Position::new(2, 37)).unwrap(),
+            Some(Range::new(1, 7, 1, 8))
+        );
+    }
+
+    #[test]
+    fn definition_of_undeclared_prefix() {
+        let state = setup_state("SELECT * WHERE { ?s wdt:P31 ?o }");
+        assert_eq!(
+            get_definition(&state, "uri", &Position::new(0, 21)).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn definition_of_variable() {
+        let state = setup_state(indoc!(
+            "SELECT ?x WHERE {
+               FILTER (?x > 1)
+               BIND (1 AS ?y)
+               ?s ?p ?x .
+               ?y ?p $x
+             }"
+        ));
+        assert_eq!(
+            get_definition(&state, "uri", &Position::new(0, 8)).unwrap(),
+            Some(Range::new(3, 8, 3, 10))
+        );
+        assert_eq!(
+            get_definition(&state, "uri", &Position::new(4, 8)).unwrap(),
+            Some(Range::new(3, 8, 3, 10))
+        );
+        assert_eq!(
+            get_definition(&state, "uri", &Position::new(4, 2)).unwrap(),
+            Some(Range::new(2, 13, 2, 15))
+        );
+    }
+
+    #[test]
+    fn definition_of_variable_in_subselect() {
+        let state = setup_state(indoc!(
+            "SELECT ?x ?y WHERE {
+               { SELECT ?x WHERE { ?x ?p ?y } }
+               ?x ?q ?y
+             }"
+        ));
+        // NOTE: ?y of the subquery is not projected, it is a different variable.
+        assert_eq!(
+            get_definition(&state, "uri", &Position::new(0, 11)).unwrap(),
+            Some(Range::new(2, 8, 2, 10))
+        );
+        assert_eq!(
+            get_definition(&state, "uri", &Position::new(1, 28)).unwrap(),
+            Some(Range::new(1, 28, 1, 30))
+        );
+        // NOTE: ?x is projected, the projection of the subquery binds it.
+        assert_eq!(
+            get_definition(&state, "uri", &Position::new(2, 2)).unwrap(),
+            Some(Range::new(1, 11, 1, 13))
+        );
+    }
+
+    #[test]
+    fn definition_and_occurrences_after_non_ascii() {
+        // NOTE: The ranges are in UTF-16 columns, "ü" is one column but two bytes.
+        let state = setup_state(indoc!(
+            "SELECT ?x WHERE { ?s ?label \"Zürich\" ; ?p ?x . ?x ?q ?o }"
+        ));
+        assert_eq!(
+            get_definition(&state, "uri", &Position::new(0, 7)).unwrap(),
+            Some(Range::new(0, 42, 0, 44))
+        );
+        assert_eq!(
+            occurrences(&state, Position::new(0, 47)),
+            vec![
+                (Range::new(0, 7, 0, 9), false),
+                (Range::new(0, 42, 0, 44), true),
+                (Range::new(0, 47, 0, 49), true),
+            ]
+        );
+    }
+
+    fn occurrences(state: &ServerState, position: Position) -> Vec<(Range, bool)> {
+        get_occurrences(state, "uri", &position)
+            .unwrap()
+            .into_iter()
+            .map(|occurrence| (occurrence.range, occurrence.is_declaration))
+            .collect()
+    }
+
+    const OCCURRENCES_QUERY: &str = indoc!(
+        "PREFIX wdt: <http://www.wikidata.org/prop/direct/>
+         SELECT ?item ?itemLabel WHERE {
+           ?item wdt:P31 ?class .
+           ?item <http://www.wikidata.org/prop/direct/P31> ?itemLabel .
+           { SELECT ?class WHERE { ?item wdt:P279 ?class } }
+         }"
+    );
+
+    #[test]
+    fn occurrences_of_variable() {
+        let state = setup_state(OCCURRENCES_QUERY);
+        assert_eq!(
+            occurrences(&state, Position::new(1, 8)),
+            vec![
+                (Range::new(1, 7, 1, 12), false),
+                (Range::new(2, 2, 2, 7), true),
+                (Range::new(3, 2, 3, 7), true),
+            ]
+        );
+        assert_eq!(
+            occurrences(&state, Position::new(4, 27)),
+            vec![(Range::new(4, 26, 4, 31), true)]
+        );
+    }
+
+    #[test]
+    fn occurrences_of_projected_variable() {
+        let state = setup_state(OCCURRENCES_QUERY);
+        assert_eq!(
+            occurrences(&state, Position::new(4, 42)),
+            vec![
+                (Range::new(2, 16, 2, 22), true),
+                (Range::new(4, 11, 4, 17), true),
+                (Range::new(4, 41, 4, 47), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn occurrences_of_prefix() {
+        let state = setup_state(OCCURRENCES_QUERY);
+        assert_eq!(
+            occurrences(&state, Position::new(2, 9)),
+            vec![
+                (Range::new(0, 7, 0, 11), true),
+                (Range::new(2, 8, 2, 12), false),
+                (Range::new(4, 32, 4, 36), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn occurrences_of_iri() {
+        let state = setup_state(OCCURRENCES_QUERY);
+        let expected = vec![
+            (Range::new(2, 8, 2, 15), false),
+            (Range::new(3, 8, 3, 49), false),
+        ];
+        assert_eq!(occurrences(&state, Position::new(2, 13)), expected);
+        assert_eq!(occurrences(&state, Position::new(3, 20)), expected);
+    }
+
+    const SCOPES_QUERY: &str = indoc!(
+        "SELECT ?s WHERE {
+           ?s ?p ?o .
+           { SELECT ?count WHERE { ?x ?y ?z } }
+           { ?s ?a ?left } UNION { ?s ?b ?right }
+           OPTIONAL { ?s ?c ?optional }
+           MINUS { ?s ?d ?excluded }
+           FILTER NOT EXISTS { ?s ?e ?tested }
+           FILTER (?bound)
+           BIND (1 AS ?bound)
+         }"
+    );
+
+    /// The variables visible at the end of the first occurrence of `marker` in `SCOPES_QUERY`.
+    fn visible_variables(marker: &str) -> Vec<String> {
+        let cursor = SCOPES_QUERY.find(marker).unwrap() + marker.len();
+        let mut parser = Parser::new();
+        parser.set_language(&LANGUAGE.into()).unwrap();
+        let tree = parser.parse(SCOPES_QUERY, None).unwrap();
+        ScopeModel::new(tree.root_node(), SCOPES_QUERY)
+            .visible_at(cursor)
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn scopes_visible_variables() {
+        // NOTE: Variables of subqueries that are not projected, of `MINUS` blocks,
+        // of `EXISTS` groups and of later `BIND`s are not visible.
+        assert_eq!(
+            visible_variables("?s ?p"),
+            vec![
+                "?a",
+                "?b",
+                "?c",
+                "?count",
+                "?left",
+                "?o",
+                "?optional",
+                "?p",
+                "?right",
+                "?s"
+            ]
+        );
+        assert_eq!(
+            visible_variables("FILTER ("),
+            vec![
+                "?a",
+                "?b",
+                "?bound",
+                "?c",
+                "?count",
+                "?left",
+                "?o",
+                "?optional",
+                "?p",
+                "?right",
+                "?s"
+            ]
+        );
+        assert_eq!(
+            visible_variables("SELECT ?count WHERE { ?x"),
+            vec!["?count", "?x", "?y", "?z"]
+        );
+        assert!(!visible_variables("{ ?s ?a").contains(&"?right".to_string()));
+        assert!(visible_variables("MINUS { ?s").contains(&"?excluded".to_string()));
+        assert!(visible_variables("EXISTS { ?s").contains(&"?tested".to_string()));
+    }
 }
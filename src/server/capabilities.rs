@@ -1,27 +1,79 @@
-use super::lsp::capabilities::{
-    CompletionOptions, DiagnosticOptions, DocumentFormattingOptions, ExecuteCommandOptions,
-    ServerCapabilities, TextDocumentSyncKind, WorkDoneProgressOptions,
+use super::{
+    configuration::{DiagnosticsMode, Settings},
+    lsp::{
+        capabilities::{
+            CompletionOptions, DiagnosticOptions, DocumentFormattingOptions,
+            DocumentOnTypeFormattingOptions, ExecuteCommandOptions, RenameOptions,
+            SemanticTokensLegend, SemanticTokensOptions, ServerCapabilities, SignatureHelpOptions,
+            TextDocumentSyncKind, WorkDoneProgressOptions,
+        },
+        SemanticTokenModifier, SemanticTokenType,
+    },
 };
 
-pub(super) fn create_capabilities() -> ServerCapabilities {
+pub(super) fn create_capabilities(settings: &Settings) -> ServerCapabilities {
     ServerCapabilities {
         text_document_sync: TextDocumentSyncKind::Incremental,
         hover_provider: true,
+        definition_provider: true,
+        references_provider: true,
+        document_highlight_provider: true,
+        rename_provider: RenameOptions {
+            prepare_provider: true,
+        },
+        document_symbol_provider: true,
+        folding_range_provider: true,
+        semantic_tokens_provider: SemanticTokensOptions {
+            legend: SemanticTokensLegend {
+                token_types: SemanticTokenType::LEGEND
+                    .iter()
+                    .map(|token_type| token_type.to_string())
+                    .collect(),
+                token_modifiers: SemanticTokenModifier::LEGEND
+                    .iter()
+                    .map(|token_modifier| token_modifier.to_string())
+                    .collect(),
+            },
+            range: true,
+            full: true,
+        },
         code_action_provider: true,
         execute_command_provider: ExecuteCommandOptions {
             work_done_progress_options: WorkDoneProgressOptions {
                 work_done_progress: true,
             },
-            commands: vec![String::from("publish diagnostics")],
+            commands: vec![
+                String::from("publish diagnostics"),
+                String::from("reloadPrefixes"),
+                String::from("reloadVocabularies"),
+            ],
         },
-        diagnostic_provider: DiagnosticOptions {
-            identifier: "qlue-ls".to_string(),
-            inter_file_dependencies: false,
-            workspace_diagnostics: false,
+        // NOTE: In push mode diagnostics are published, the client should not pull them.
+        diagnostic_provider: match settings.diagnostics.mode {
+            DiagnosticsMode::Pull => Some(DiagnosticOptions {
+                identifier: "qlue-ls".to_string(),
+                inter_file_dependencies: false,
+                workspace_diagnostics: false,
+            }),
+            DiagnosticsMode::Push => None,
         },
         completion_provider: CompletionOptions {
-            trigger_characters: vec!["?".to_string()],
+            trigger_characters: vec![
+                "?".to_string(),
+                ":".to_string(),
+                "@".to_string(),
+                "^".to_string(),
+            ],
+            resolve_provider: Some(true),
+        },
+        signature_help_provider: SignatureHelpOptions {
+            trigger_characters: vec!["(".to_string(), ",".to_string()],
         },
         document_formatting_provider: DocumentFormattingOptions {},
+        document_range_formatting_provider: true,
+        document_on_type_formatting_provider: DocumentOnTypeFormattingOptions {
+            first_trigger_character: "}".to_string(),
+            more_trigger_character: vec![".".to_string(), ";".to_string()],
+        },
     }
 }
@@ -30,9 +30,79 @@ impl Default for FormatSettings {
     }
 }
 
+/// How diagnostics are delivered to the client.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticsMode {
+    /// The client requests diagnostics (`textDocument/diagnostic`).
+    Pull,
+    /// The server publishes diagnostics after a document was opened or changed
+    /// (`textDocument/publishDiagnostics`).
+    Push,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiagnosticsSettings {
+    pub mode: DiagnosticsMode,
+    /// In push mode, the diagnostics of a changed document are published once it did not
+    /// change for this many milliseconds.
+    pub debounce_ms: u64,
+}
+
+impl Default for DiagnosticsSettings {
+    fn default() -> Self {
+        Self {
+            mode: DiagnosticsMode::Pull,
+            debounce_ms: 300,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Settings {
     pub format: FormatSettings,
+    #[serde(default)]
+    pub diagnostics: DiagnosticsSettings,
+    #[serde(default)]
+    pub builtin_prefixes: BuiltinPrefixesSettings,
+    /// Files to load prefixes from (prefix.cc-style JSON, JSON-LD `@context` or Turtle).
+    #[serde(default)]
+    pub prefix_files: Vec<String>,
+    /// Inline prefix declarations, these override the prefixes from files.
+    #[serde(default)]
+    pub prefixes: Vec<PrefixSetting>,
+    /// Vocabulary files (Turtle, N-Triples or RDF/XML) to index classes and properties from.
+    #[serde(default)]
+    pub vocabulary_files: Vec<String>,
+}
+
+/// Controls the prefixes of the bundled snapshot.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BuiltinPrefixesSettings {
+    pub enabled: bool,
+    /// Prefixes of the snapshot that should not be used.
+    pub disabled: Vec<String>,
+}
+
+impl Default for BuiltinPrefixesSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            disabled: vec![],
+        }
+    }
+}
+
+/// A single prefix declaration.
+///
+/// NOTE: Prefixes are case-sensitive but the keys of the configuration are not,
+/// so the prefix is a value and not a key.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PrefixSetting {
+    pub prefix: String,
+    pub namespace: String,
 }
 
 fn load_user_configuration() -> Result<Settings, ConfigError> {
@@ -64,6 +134,11 @@ impl Default for Settings {
     fn default() -> Self {
         Self {
             format: Default::default(),
+            diagnostics: Default::default(),
+            builtin_prefixes: Default::default(),
+            prefix_files: Default::default(),
+            prefixes: Default::default(),
+            vocabulary_files: Default::default(),
         }
     }
 }
@@ -6,9 +6,20 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 pub struct ServerCapabilities {
     pub text_document_sync: TextDocumentSyncKind,
     pub hover_provider: bool,
+    pub definition_provider: bool,
+    pub references_provider: bool,
+    pub document_highlight_provider: bool,
+    pub rename_provider: RenameOptions,
+    pub document_symbol_provider: bool,
+    pub folding_range_provider: bool,
+    pub semantic_tokens_provider: SemanticTokensOptions,
     pub completion_provider: CompletionOptions,
+    pub signature_help_provider: SignatureHelpOptions,
     pub document_formatting_provider: DocumentFormattingOptions,
-    pub diagnostic_provider: DiagnosticOptions,
+    pub document_range_formatting_provider: bool,
+    pub document_on_type_formatting_provider: DocumentOnTypeFormattingOptions,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diagnostic_provider: Option<DiagnosticOptions>,
     pub code_action_provider: bool,
     pub execute_command_provider: ExecuteCommandOptions,
 }
@@ -48,6 +59,39 @@ pub struct CompletionOptions {
     // WARNING: This is not to spec, there are more optional options:
     // https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#completionOptions
     pub trigger_characters: Vec<String>,
+    /// The server resolves additional information for a completion item
+    /// (`completionItem/resolve`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolve_provider: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureHelpOptions {
+    pub trigger_characters: Vec<String>,
+    // NOTE: there are more optional options:
+    // retriggerCharacters: string[]
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameOptions {
+    pub prepare_provider: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SemanticTokensOptions {
+    pub legend: SemanticTokensLegend,
+    pub range: bool,
+    pub full: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SemanticTokensLegend {
+    pub token_types: Vec<String>,
+    pub token_modifiers: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -55,12 +99,21 @@ pub struct DocumentFormattingOptions {
     // WARNING: This could also inherit WorkDoneProgressOptions (not implemented yet).
 }
 
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentOnTypeFormattingOptions {
+    pub first_trigger_character: String,
+    pub more_trigger_character: Vec<String>,
+}
+
 #[cfg(test)]
 mod tests {
 
     use crate::server::lsp::capabilities::{
-        CompletionOptions, DiagnosticOptions, DocumentFormattingOptions, ExecuteCommandOptions,
-        TextDocumentSyncKind, WorkDoneProgressOptions,
+        CompletionOptions, DiagnosticOptions, DocumentFormattingOptions,
+        DocumentOnTypeFormattingOptions, ExecuteCommandOptions, RenameOptions,
+        SemanticTokensLegend, SemanticTokensOptions, SignatureHelpOptions, TextDocumentSyncKind,
+        WorkDoneProgressOptions,
     };
 
     use super::ServerCapabilities;
@@ -70,15 +123,40 @@ mod tests {
         let server_capabilities = ServerCapabilities {
             text_document_sync: TextDocumentSyncKind::Full,
             hover_provider: true,
+            definition_provider: true,
+            references_provider: true,
+            document_highlight_provider: true,
+            rename_provider: RenameOptions {
+                prepare_provider: true,
+            },
+            document_symbol_provider: true,
+            folding_range_provider: true,
+            semantic_tokens_provider: SemanticTokensOptions {
+                legend: SemanticTokensLegend {
+                    token_types: vec!["keyword".to_string()],
+                    token_modifiers: vec!["declaration".to_string()],
+                },
+                range: true,
+                full: true,
+            },
             completion_provider: CompletionOptions {
                 trigger_characters: vec!["?".to_string()],
+                resolve_provider: None,
+            },
+            signature_help_provider: SignatureHelpOptions {
+                trigger_characters: vec!["(".to_string()],
             },
             document_formatting_provider: DocumentFormattingOptions {},
-            diagnostic_provider: DiagnosticOptions {
+            document_range_formatting_provider: true,
+            document_on_type_formatting_provider: DocumentOnTypeFormattingOptions {
+                first_trigger_character: "}".to_string(),
+                more_trigger_character: vec![".".to_string()],
+            },
+            diagnostic_provider: Some(DiagnosticOptions {
                 identifier: "my-ls".to_string(),
                 inter_file_dependencies: false,
                 workspace_diagnostics: false,
-            },
+            }),
             code_action_provider: true,
             execute_command_provider: ExecuteCommandOptions {
                 work_done_progress_options: WorkDoneProgressOptions {
@@ -92,7 +170,7 @@ mod tests {
 
         assert_eq!(
             serialized,
-            r#"{"textDocumentSync":1,"hoverProvider":true,"completionProvider":{"triggerCharacters":["?"]},"documentFormattingProvider":{},"diagnosticProvider":{"identifier":"my-ls","interFileDependencies":false,"workspaceDiagnostics":false},"codeActionProvider":true,"executeCommandProvider":{"workDoneProgress":true,"commands":["foo"]}}"#
+            r#"{"textDocumentSync":1,"hoverProvider":true,"definitionProvider":true,"referencesProvider":true,"documentHighlightProvider":true,"renameProvider":{"prepareProvider":true},"documentSymbolProvider":true,"foldingRangeProvider":true,"semanticTokensProvider":{"legend":{"tokenTypes":["keyword"],"tokenModifiers":["declaration"]},"range":true,"full":true},"completionProvider":{"triggerCharacters":["?"]},"signatureHelpProvider":{"triggerCharacters":["("]},"documentFormattingProvider":{},"documentRangeFormattingProvider":true,"documentOnTypeFormattingProvider":{"firstTriggerCharacter":"}","moreTriggerCharacter":["."]},"diagnosticProvider":{"identifier":"my-ls","interFileDependencies":false,"workspaceDiagnostics":false},"codeActionProvider":true,"executeCommandProvider":{"workDoneProgress":true,"commands":["foo"]}}"#
         );
     }
 }
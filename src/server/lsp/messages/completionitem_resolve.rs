@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+use crate::server::lsp::rpc::{RequestId, RequestMessageBase, ResponseMessageBase};
+
+use super::CompletionItem;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct CompletionResolveRequest {
+    #[serde(flatten)]
+    base: RequestMessageBase,
+    pub params: CompletionItem,
+}
+
+impl CompletionResolveRequest {
+    pub(crate) fn get_id(&self) -> &RequestId {
+        &self.base.id
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct CompletionResolveResponse {
+    #[serde(flatten)]
+    base: ResponseMessageBase,
+    result: CompletionItem,
+}
+
+impl CompletionResolveResponse {
+    pub fn new(id: &RequestId, item: CompletionItem) -> Self {
+        CompletionResolveResponse {
+            base: ResponseMessageBase::success(id),
+            result: item,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::server::lsp::{base_types::LSPAny, rpc::RequestId};
+
+    use super::{CompletionResolveRequest, CompletionResolveResponse};
+
+    #[test]
+    fn deserialize_and_serialize() {
+        let message = br#"{"id":2,"params":{"label":"knows","kind":10,"insertText":"ex:knows","insertTextFormat":1,"data":{"iri":"http://example.org/knows"}},"jsonrpc":"2.0","method":"completionItem/resolve"}"#;
+        let mut request: CompletionResolveRequest = serde_json::from_slice(message).unwrap();
+        assert_eq!(request.get_id(), &RequestId::Integer(2));
+        assert_eq!(request.params.written_text(), "ex:knows");
+        assert!(matches!(request.params.data, Some(LSPAny::LSPObject(_))));
+        assert_eq!(request.params.detail, None);
+        request.params.detail = Some("ex:knows".to_string());
+        let response = CompletionResolveResponse::new(&RequestId::Integer(2), request.params);
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"jsonrpc":"2.0","id":2,"result":{"label":"knows","kind":10,"detail":"ex:knows","insertText":"ex:knows","insertTextFormat":1,"data":{"iri":"http://example.org/knows"}}}"#
+        );
+    }
+}
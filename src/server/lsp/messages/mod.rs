@@ -1,32 +1,52 @@
+mod completionitem_resolve;
 pub mod diagnostic;
 mod initialize;
 mod progress;
 mod shutdown;
 mod textdocument_codeaction;
 mod textdocument_completion;
+mod textdocument_definition;
 mod textdocument_diagnostic;
 mod textdocument_didchange;
+mod textdocument_didclose;
 mod textdocument_didopen;
 mod textdocument_didsave;
+mod textdocument_documenthighlight;
+mod textdocument_documentsymbol;
+mod textdocument_foldingrange;
 mod textdocument_formatting;
 mod textdocument_hover;
 mod textdocument_publishdiagnostics;
+mod textdocument_references;
+mod textdocument_rename;
+mod textdocument_semantictokens;
+mod textdocument_signaturehelp;
 mod trace;
 mod utils;
 mod window_showmessage;
 mod workspace_exectutecommand;
 
+pub use completionitem_resolve::*;
 pub use initialize::*;
 pub use progress::*;
 pub use shutdown::*;
 pub use textdocument_codeaction::*;
 pub use textdocument_completion::*;
+pub use textdocument_definition::*;
 pub use textdocument_diagnostic::*;
 pub use textdocument_didchange::*;
+pub use textdocument_didclose::*;
 pub use textdocument_didopen::*;
 pub use textdocument_didsave::*;
+pub use textdocument_documenthighlight::*;
+pub use textdocument_documentsymbol::*;
+pub use textdocument_foldingrange::*;
 pub use textdocument_formatting::*;
 pub use textdocument_hover::*;
 pub use textdocument_publishdiagnostics::*;
+pub use textdocument_references::*;
+pub use textdocument_rename::*;
+pub use textdocument_semantictokens::*;
+pub use textdocument_signaturehelp::*;
 pub use trace::*;
 pub use workspace_exectutecommand::*;
@@ -127,7 +127,7 @@ impl CodeAction {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct WorkspaceEdit {
     pub changes: HashMap<DocumentUri, Vec<TextEdit>>,
 }
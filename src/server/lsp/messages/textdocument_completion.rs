@@ -1,9 +1,13 @@
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
-use crate::server::lsp::rpc::{RequestId, RequestMessageBase, ResponseMessageBase};
+use crate::server::lsp::{
+    base_types::LSPAny,
+    rpc::{RequestId, RequestMessageBase, ResponseMessageBase},
+    textdocument::TextEdit,
+};
 
-use super::utils::TextDocumentPositionParams;
+use super::{utils::TextDocumentPositionParams, Markupkind};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct CompletionRequest {
@@ -74,9 +78,28 @@ struct CompletionResult {
 pub struct CompletionItem {
     label: String,
     kind: CompletionItemKind,
-    detail: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub detail: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub documentation: Option<MarkupContent>,
+    /// Sorts the item among the other items, the `label` is used if not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_text: Option<String>,
+    /// Matched against the typed word, the `label` is used if not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter_text: Option<String>,
     insert_text: String,
     insert_text_format: InsertTextFormat,
+    /// Replaces the range of the edit with the completion, takes precedence over `insert_text`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub text_edit: Option<TextEdit>,
+    /// Edits applied after inserting the completion, that do not touch the cursor position
+    /// (e.g. declaring a prefix).
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub additional_text_edits: Vec<TextEdit>,
+    /// Preserved between a completion request and a `completionItem/resolve` request.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub data: Option<LSPAny>,
 }
 
 impl CompletionItem {
@@ -90,9 +113,38 @@ impl CompletionItem {
         Self {
             label: label.to_string(),
             kind,
-            detail: detail.to_string(),
+            detail: Some(detail.to_string()),
+            documentation: None,
+            sort_text: None,
+            filter_text: None,
             insert_text: insert_text.to_string(),
             insert_text_format,
+            text_edit: None,
+            additional_text_edits: vec![],
+            data: None,
+        }
+    }
+
+    /// The text this item writes into the document.
+    pub(crate) fn written_text(&self) -> &str {
+        match &self.text_edit {
+            Some(text_edit) => &text_edit.new_text,
+            None => &self.insert_text,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct MarkupContent {
+    pub kind: Markupkind,
+    pub value: String,
+}
+
+impl MarkupContent {
+    pub fn markdown(value: &str) -> Self {
+        Self {
+            kind: Markupkind::Markdown,
+            value: value.to_string(),
         }
     }
 }
@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+use crate::server::lsp::{
+    rpc::{RequestId, RequestMessageBase, ResponseMessageBase},
+    textdocument::{Location, Position},
+};
+
+use super::utils::TextDocumentPositionParams;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct DefinitionRequest {
+    #[serde(flatten)]
+    base: RequestMessageBase,
+    params: DefinitionParams,
+}
+
+impl DefinitionRequest {
+    pub fn get_position(&self) -> &Position {
+        &self.params.text_document_position.position
+    }
+
+    pub fn get_document_uri(&self) -> &String {
+        &self.params.text_document_position.text_document.uri
+    }
+
+    pub(crate) fn get_id(&self) -> &RequestId {
+        &self.base.id
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct DefinitionParams {
+    #[serde(flatten)]
+    text_document_position: TextDocumentPositionParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct DefinitionResponse {
+    #[serde(flatten)]
+    base: ResponseMessageBase,
+    result: Option<Location>,
+}
+
+impl DefinitionResponse {
+    pub fn new(id: &RequestId, location: Option<Location>) -> Self {
+        Self {
+            base: ResponseMessageBase::success(id),
+            result: location,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::server::lsp::{
+        messages::{textdocument_definition::DefinitionParams, utils::TextDocumentPositionParams},
+        rpc::{Message, RequestId, RequestMessageBase},
+        textdocument::{Location, Position, Range, TextDocumentIdentifier},
+    };
+
+    use super::{DefinitionRequest, DefinitionResponse};
+
+    #[test]
+    fn deserialize() {
+        let message = br#"{"params":{"textDocument":{"uri":"file:///dings"},"position":{"character":7,"line":2}},"method":"textDocument/definition","id":3,"jsonrpc":"2.0"}"#;
+        let definition_request: DefinitionRequest = serde_json::from_slice(message).unwrap();
+        assert_eq!(
+            definition_request,
+            DefinitionRequest {
+                base: RequestMessageBase {
+                    base: Message {
+                        jsonrpc: "2.0".to_string(),
+                    },
+                    method: "textDocument/definition".to_string(),
+                    id: RequestId::Integer(3)
+                },
+                params: DefinitionParams {
+                    text_document_position: TextDocumentPositionParams {
+                        text_document: TextDocumentIdentifier {
+                            uri: "file:///dings".to_string()
+                        },
+                        position: Position::new(2, 7)
+                    }
+                }
+            }
+        )
+    }
+
+    #[test]
+    fn serialize() {
+        let definition_response = DefinitionResponse::new(
+            &RequestId::Integer(42),
+            Some(Location::new("file:///dings", Range::new(0, 7, 0, 9))),
+        );
+        assert_eq!(
+            serde_json::to_string(&definition_response).unwrap(),
+            r#"{"jsonrpc":"2.0","id":42,"result":{"uri":"file:///dings","range":{"start":{"line":0,"character":7},"end":{"line":0,"character":9}}}}"#
+        );
+        let empty_response = DefinitionResponse::new(&RequestId::Integer(42), None);
+        assert_eq!(
+            serde_json::to_string(&empty_response).unwrap(),
+            r#"{"jsonrpc":"2.0","id":42,"result":null}"#
+        );
+    }
+}
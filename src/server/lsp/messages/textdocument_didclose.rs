@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+use crate::server::lsp::{rpc::NotificationMessage, textdocument::TextDocumentIdentifier};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct DidCloseTextDocumentNotification {
+    #[serde(flatten)]
+    base: NotificationMessage,
+    pub params: DidCloseTextDocumentParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DidCloseTextDocumentParams {
+    pub text_document: TextDocumentIdentifier,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::server::lsp::textdocument::TextDocumentIdentifier;
+
+    use super::DidCloseTextDocumentNotification;
+
+    #[test]
+    fn deserialize() {
+        let message = br#"{"jsonrpc":"2.0","method":"textDocument/didClose","params":{"textDocument":{"uri":"file:///query.rq"}}}"#;
+        let notification: DidCloseTextDocumentNotification =
+            serde_json::from_slice(message).unwrap();
+        assert_eq!(
+            notification.params.text_document,
+            TextDocumentIdentifier {
+                uri: "file:///query.rq".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+use crate::server::lsp::{
+    rpc::{RequestId, RequestMessageBase, ResponseMessageBase},
+    textdocument::{Position, Range},
+};
+
+use super::utils::TextDocumentPositionParams;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct DocumentHighlightRequest {
+    #[serde(flatten)]
+    base: RequestMessageBase,
+    params: DocumentHighlightParams,
+}
+
+impl DocumentHighlightRequest {
+    pub fn get_position(&self) -> &Position {
+        &self.params.text_document_position.position
+    }
+
+    pub fn get_document_uri(&self) -> &String {
+        &self.params.text_document_position.text_document.uri
+    }
+
+    pub(crate) fn get_id(&self) -> &RequestId {
+        &self.base.id
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct DocumentHighlightParams {
+    #[serde(flatten)]
+    text_document_position: TextDocumentPositionParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct DocumentHighlightResponse {
+    #[serde(flatten)]
+    base: ResponseMessageBase,
+    result: Option<Vec<DocumentHighlight>>,
+}
+
+impl DocumentHighlightResponse {
+    pub fn new(id: &RequestId, highlights: Option<Vec<DocumentHighlight>>) -> Self {
+        Self {
+            base: ResponseMessageBase::success(id),
+            result: highlights,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct DocumentHighlight {
+    range: Range,
+    kind: DocumentHighlightKind,
+}
+
+impl DocumentHighlight {
+    pub fn new(range: Range, kind: DocumentHighlightKind) -> Self {
+        Self { range, kind }
+    }
+}
+
+#[derive(Debug, Serialize_repr, Deserialize_repr, PartialEq)]
+#[repr(u8)]
+pub enum DocumentHighlightKind {
+    Text = 1,
+    Read = 2,
+    Write = 3,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::server::lsp::{
+        messages::{
+            textdocument_documenthighlight::DocumentHighlightParams,
+            utils::TextDocumentPositionParams,
+        },
+        rpc::{Message, RequestId, RequestMessageBase},
+        textdocument::{Position, Range, TextDocumentIdentifier},
+    };
+
+    use super::{
+        DocumentHighlight, DocumentHighlightKind, DocumentHighlightRequest,
+        DocumentHighlightResponse,
+    };
+
+    #[test]
+    fn deserialize() {
+        let message = br#"{"params":{"textDocument":{"uri":"file:///dings"},"position":{"character":7,"line":2}},"method":"textDocument/documentHighlight","id":3,"jsonrpc":"2.0"}"#;
+        let highlight_request: DocumentHighlightRequest = serde_json::from_slice(message).unwrap();
+        assert_eq!(
+            highlight_request,
+            DocumentHighlightRequest {
+                base: RequestMessageBase {
+                    base: Message {
+                        jsonrpc: "2.0".to_string(),
+                    },
+                    method: "textDocument/documentHighlight".to_string(),
+                    id: RequestId::Integer(3)
+                },
+                params: DocumentHighlightParams {
+                    text_document_position: TextDocumentPositionParams {
+                        text_document: TextDocumentIdentifier {
+                            uri: "file:///dings".to_string()
+                        },
+                        position: Position::new(2, 7)
+                    }
+                }
+            }
+        )
+    }
+
+    #[test]
+    fn serialize() {
+        let highlight_response = DocumentHighlightResponse::new(
+            &RequestId::Integer(42),
+            Some(vec![
+                DocumentHighlight::new(Range::new(0, 7, 0, 9), DocumentHighlightKind::Write),
+                DocumentHighlight::new(Range::new(1, 2, 1, 4), DocumentHighlightKind::Read),
+            ]),
+        );
+        assert_eq!(
+            serde_json::to_string(&highlight_response).unwrap(),
+            r#"{"jsonrpc":"2.0","id":42,"result":[{"range":{"start":{"line":0,"character":7},"end":{"line":0,"character":9}},"kind":3},{"range":{"start":{"line":1,"character":2},"end":{"line":1,"character":4}},"kind":2}]}"#
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+use crate::server::lsp::{
+    rpc::{RequestId, RequestMessageBase, ResponseMessageBase},
+    textdocument::{Range, TextDocumentIdentifier},
+};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct DocumentSymbolRequest {
+    #[serde(flatten)]
+    base: RequestMessageBase,
+    params: DocumentSymbolParams,
+}
+
+impl DocumentSymbolRequest {
+    pub fn get_document_uri(&self) -> &String {
+        &self.params.text_document.uri
+    }
+
+    pub(crate) fn get_id(&self) -> &RequestId {
+        &self.base.id
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct DocumentSymbolParams {
+    text_document: TextDocumentIdentifier,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct DocumentSymbolResponse {
+    #[serde(flatten)]
+    base: ResponseMessageBase,
+    result: Vec<DocumentSymbol>,
+}
+
+impl DocumentSymbolResponse {
+    pub fn new(id: &RequestId, symbols: Vec<DocumentSymbol>) -> Self {
+        Self {
+            base: ResponseMessageBase::success(id),
+            result: symbols,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentSymbol {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    pub kind: SymbolKind,
+    /// The range enclosing the whole symbol, used to determine if the cursor is inside the symbol.
+    pub range: Range,
+    /// The range that should be selected when the symbol is picked, e.g. the name of the symbol.
+    /// Must be contained by `range`.
+    pub selection_range: Range,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub children: Vec<DocumentSymbol>,
+    // NOTE: there are more optional options:
+    // tags: SymbolTag[]
+}
+
+#[derive(Debug, Serialize_repr, Deserialize_repr, PartialEq)]
+#[repr(u8)]
+pub enum SymbolKind {
+    File = 1,
+    Module = 2,
+    Namespace = 3,
+    Package = 4,
+    Class = 5,
+    Method = 6,
+    Property = 7,
+    Field = 8,
+    Constructor = 9,
+    Enum = 10,
+    Interface = 11,
+    Function = 12,
+    Variable = 13,
+    Constant = 14,
+    String = 15,
+    Number = 16,
+    Boolean = 17,
+    Array = 18,
+    Object = 19,
+    Key = 20,
+    Null = 21,
+    EnumMember = 22,
+    Struct = 23,
+    Event = 24,
+    Operator = 25,
+    TypeParameter = 26,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::server::lsp::{
+        messages::textdocument_documentsymbol::DocumentSymbolParams,
+        rpc::{Message, RequestId, RequestMessageBase},
+        textdocument::{Range, TextDocumentIdentifier},
+    };
+
+    use super::{DocumentSymbol, DocumentSymbolRequest, DocumentSymbolResponse, SymbolKind};
+
+    #[test]
+    fn deserialize() {
+        let message = br#"{"params":{"textDocument":{"uri":"file:///dings"}},"method":"textDocument/documentSymbol","id":3,"jsonrpc":"2.0"}"#;
+        let document_symbol_request: DocumentSymbolRequest =
+            serde_json::from_slice(message).unwrap();
+        assert_eq!(
+            document_symbol_request,
+            DocumentSymbolRequest {
+                base: RequestMessageBase {
+                    base: Message {
+                        jsonrpc: "2.0".to_string(),
+                    },
+                    method: "textDocument/documentSymbol".to_string(),
+                    id: RequestId::Integer(3)
+                },
+                params: DocumentSymbolParams {
+                    text_document: TextDocumentIdentifier {
+                        uri: "file:///dings".to_string()
+                    }
+                }
+            }
+        )
+    }
+
+    #[test]
+    fn serialize() {
+        let document_symbol_response = DocumentSymbolResponse::new(
+            &RequestId::Integer(42),
+            vec![DocumentSymbol {
+                name: "SELECT".to_string(),
+                detail: Some("?s".to_string()),
+                kind: SymbolKind::Function,
+                range: Range::new(0, 0, 0, 20),
+                selection_range: Range::new(0, 0, 0, 6),
+                children: vec![DocumentSymbol {
+                    name: "?s".to_string(),
+                    detail: None,
+                    kind: SymbolKind::Variable,
+                    range: Range::new(0, 7, 0, 9),
+                    selection_range: Range::new(0, 7, 0, 9),
+                    children: vec![],
+                }],
+            }],
+        );
+        assert_eq!(
+            serde_json::to_string(&document_symbol_response).unwrap(),
+            r#"{"jsonrpc":"2.0","id":42,"result":[{"name":"SELECT","detail":"?s","kind":12,"range":{"start":{"line":0,"character":0},"end":{"line":0,"character":20}},"selectionRange":{"start":{"line":0,"character":0},"end":{"line":0,"character":6}},"children":[{"name":"?s","kind":13,"range":{"start":{"line":0,"character":7},"end":{"line":0,"character":9}},"selectionRange":{"start":{"line":0,"character":7},"end":{"line":0,"character":9}}}]}]}"#
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+use crate::server::lsp::{
+    rpc::{RequestId, RequestMessageBase, ResponseMessageBase},
+    textdocument::TextDocumentIdentifier,
+};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct FoldingRangeRequest {
+    #[serde(flatten)]
+    base: RequestMessageBase,
+    params: FoldingRangeParams,
+}
+
+impl FoldingRangeRequest {
+    pub fn get_document_uri(&self) -> &String {
+        &self.params.text_document.uri
+    }
+
+    pub(crate) fn get_id(&self) -> &RequestId {
+        &self.base.id
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct FoldingRangeParams {
+    text_document: TextDocumentIdentifier,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct FoldingRangeResponse {
+    #[serde(flatten)]
+    base: ResponseMessageBase,
+    result: Vec<FoldingRange>,
+}
+
+impl FoldingRangeResponse {
+    pub fn new(id: &RequestId, folding_ranges: Vec<FoldingRange>) -> Self {
+        Self {
+            base: ResponseMessageBase::success(id),
+            result: folding_ranges,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FoldingRange {
+    pub start_line: u32,
+    pub end_line: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<FoldingRangeKind>,
+    // NOTE: there are more optional options:
+    // startCharacter: uinteger
+    // endCharacter: uinteger
+    // collapsedText: string
+}
+
+impl FoldingRange {
+    pub fn new(start_line: u32, end_line: u32, kind: Option<FoldingRangeKind>) -> Self {
+        Self {
+            start_line,
+            end_line,
+            kind,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FoldingRangeKind {
+    Comment,
+    Imports,
+    Region,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::server::lsp::{
+        messages::textdocument_foldingrange::FoldingRangeParams,
+        rpc::{Message, RequestId, RequestMessageBase},
+        textdocument::TextDocumentIdentifier,
+    };
+
+    use super::{FoldingRange, FoldingRangeKind, FoldingRangeRequest, FoldingRangeResponse};
+
+    #[test]
+    fn deserialize() {
+        let message = br#"{"params":{"textDocument":{"uri":"file:///dings"}},"method":"textDocument/foldingRange","id":3,"jsonrpc":"2.0"}"#;
+        let folding_range_request: FoldingRangeRequest = serde_json::from_slice(message).unwrap();
+        assert_eq!(
+            folding_range_request,
+            FoldingRangeRequest {
+                base: RequestMessageBase {
+                    base: Message {
+                        jsonrpc: "2.0".to_string(),
+                    },
+                    method: "textDocument/foldingRange".to_string(),
+                    id: RequestId::Integer(3)
+                },
+                params: FoldingRangeParams {
+                    text_document: TextDocumentIdentifier {
+                        uri: "file:///dings".to_string()
+                    }
+                }
+            }
+        )
+    }
+
+    #[test]
+    fn serialize() {
+        let folding_range_response = FoldingRangeResponse::new(
+            &RequestId::Integer(42),
+            vec![
+                FoldingRange::new(0, 3, Some(FoldingRangeKind::Imports)),
+                FoldingRange::new(4, 8, None),
+            ],
+        );
+        assert_eq!(
+            serde_json::to_string(&folding_range_response).unwrap(),
+            r#"{"jsonrpc":"2.0","id":42,"result":[{"startLine":0,"endLine":3,"kind":"imports"},{"startLine":4,"endLine":8}]}"#
+        );
+    }
+}
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::server::lsp::{
     rpc::{RequestId, RequestMessageBase, ResponseMessageBase},
-    textdocument::{TextDocumentIdentifier, TextEdit},
+    textdocument::{Position, Range, TextDocumentIdentifier, TextEdit},
 };
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
@@ -32,6 +32,77 @@ struct DocumentFormattingParams {
     options: FormattingOptions,
 }
 
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct RangeFormattingRequest {
+    #[serde(flatten)]
+    base: RequestMessageBase,
+    params: DocumentRangeFormattingParams,
+}
+
+impl RangeFormattingRequest {
+    pub(crate) fn get_id(&self) -> &RequestId {
+        &self.base.id
+    }
+
+    pub fn get_document_uri(&self) -> &String {
+        &self.params.text_document.uri
+    }
+
+    pub fn get_range(&self) -> &Range {
+        &self.params.range
+    }
+
+    pub(crate) fn get_options(&self) -> &FormattingOptions {
+        &self.params.options
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct DocumentRangeFormattingParams {
+    text_document: TextDocumentIdentifier,
+    range: Range,
+    options: FormattingOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct OnTypeFormattingRequest {
+    #[serde(flatten)]
+    base: RequestMessageBase,
+    params: DocumentOnTypeFormattingParams,
+}
+
+impl OnTypeFormattingRequest {
+    pub(crate) fn get_id(&self) -> &RequestId {
+        &self.base.id
+    }
+
+    pub fn get_document_uri(&self) -> &String {
+        &self.params.text_document.uri
+    }
+
+    pub fn get_position(&self) -> &Position {
+        &self.params.position
+    }
+
+    pub fn get_trigger_character(&self) -> &str {
+        &self.params.ch
+    }
+
+    pub(crate) fn get_options(&self) -> &FormattingOptions {
+        &self.params.options
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct DocumentOnTypeFormattingParams {
+    text_document: TextDocumentIdentifier,
+    position: Position,
+    ch: String,
+    options: FormattingOptions,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct FormattingOptions {
@@ -59,13 +130,16 @@ impl FormattingResponse {
 #[cfg(test)]
 mod tests {
     use crate::server::lsp::{
-        messages::textdocument_formatting::{DocumentFormattingParams, FormattingOptions},
+        messages::textdocument_formatting::{
+            DocumentFormattingParams, DocumentOnTypeFormattingParams,
+            DocumentRangeFormattingParams, FormattingOptions,
+        },
         rpc::{Message, RequestId, RequestMessageBase},
-        textdocument::{Range, TextDocumentIdentifier, TextEdit},
+        textdocument::{Position, Range, TextDocumentIdentifier, TextEdit},
         FormattingResponse,
     };
 
-    use super::FormattingRequest;
+    use super::{FormattingRequest, OnTypeFormattingRequest, RangeFormattingRequest};
 
     #[test]
     fn deserialize() {
@@ -95,6 +169,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn deserialize_range_formatting() {
+        let message = br#"{"jsonrpc":"2.0","method":"textDocument/rangeFormatting","id":2,"params":{"textDocument":{"uri":"file:///dings"},"range":{"start":{"line":1,"character":0},"end":{"line":3,"character":4}},"options":{"tabSize":2,"insertSpaces":true}}}"#;
+        let request = serde_json::from_slice::<RangeFormattingRequest>(message).unwrap();
+
+        assert_eq!(
+            request,
+            RangeFormattingRequest {
+                base: RequestMessageBase {
+                    base: Message {
+                        jsonrpc: "2.0".to_string(),
+                    },
+                    method: "textDocument/rangeFormatting".to_string(),
+                    id: RequestId::Integer(2)
+                },
+                params: DocumentRangeFormattingParams {
+                    text_document: TextDocumentIdentifier {
+                        uri: "file:///dings".to_string()
+                    },
+                    range: Range::new(1, 0, 3, 4),
+                    options: FormattingOptions {
+                        tab_size: 2,
+                        insert_spaces: true
+                    }
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_on_type_formatting() {
+        let message = br#"{"jsonrpc":"2.0","method":"textDocument/onTypeFormatting","id":2,"params":{"textDocument":{"uri":"file:///dings"},"position":{"line":3,"character":1},"ch":"}","options":{"tabSize":2,"insertSpaces":true}}}"#;
+        let request = serde_json::from_slice::<OnTypeFormattingRequest>(message).unwrap();
+
+        assert_eq!(
+            request,
+            OnTypeFormattingRequest {
+                base: RequestMessageBase {
+                    base: Message {
+                        jsonrpc: "2.0".to_string(),
+                    },
+                    method: "textDocument/onTypeFormatting".to_string(),
+                    id: RequestId::Integer(2)
+                },
+                params: DocumentOnTypeFormattingParams {
+                    text_document: TextDocumentIdentifier {
+                        uri: "file:///dings".to_string()
+                    },
+                    position: Position::new(3, 1),
+                    ch: "}".to_string(),
+                    options: FormattingOptions {
+                        tab_size: 2,
+                        insert_spaces: true
+                    }
+                }
+            }
+        );
+    }
+
     #[test]
     fn serialize() {
         let text_edits = vec![TextEdit::new(Range::new(0, 1, 2, 3), "dings")];
@@ -38,19 +38,19 @@ struct HoverParams {
 pub struct HoverResponse {
     #[serde(flatten)]
     base: ResponseMessageBase,
-    result: HoverResult,
+    result: Option<HoverResult>,
 }
 
 impl HoverResponse {
-    pub fn new(id: &RequestId, content: String) -> Self {
+    pub fn new(id: &RequestId, content: Option<String>) -> Self {
         HoverResponse {
             base: ResponseMessageBase::success(id),
-            result: HoverResult {
+            result: content.map(|content| HoverResult {
                 contents: HoverResultContents::MarkupContent(MarkupContent::Content {
                     kind: Markupkind::Markdown,
                     value: content,
                 }),
-            },
+            }),
         }
     }
 }
@@ -130,11 +130,16 @@ mod tests {
     #[test]
     fn serialize() {
         let hover_response =
-            HoverResponse::new(&RequestId::Integer(42), "hover content".to_string());
+            HoverResponse::new(&RequestId::Integer(42), Some("hover content".to_string()));
         let expected_message = r#"{"jsonrpc":"2.0","id":42,"result":{"contents":{"kind":"markdown","value":"hover content"}}}"#;
         assert_eq!(
             serde_json::to_string(&hover_response).unwrap(),
             expected_message
         );
+        let empty_response = HoverResponse::new(&RequestId::Integer(42), None);
+        assert_eq!(
+            serde_json::to_string(&empty_response).unwrap(),
+            r#"{"jsonrpc":"2.0","id":42,"result":null}"#
+        );
     }
 }
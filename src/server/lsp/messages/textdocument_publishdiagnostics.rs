@@ -14,5 +14,8 @@ pub struct PublishDiagnosticsNotification {
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct PublishDiagnosticsPrarams {
     pub uri: String,
+    /// The version of the document the diagnostics were computed for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<u32>,
     pub diagnostics: Vec<Diagnostic>,
 }
@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+
+use crate::server::lsp::{
+    rpc::{RequestId, RequestMessageBase, ResponseMessageBase},
+    textdocument::{Location, Position},
+};
+
+use super::utils::TextDocumentPositionParams;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ReferencesRequest {
+    #[serde(flatten)]
+    base: RequestMessageBase,
+    params: ReferenceParams,
+}
+
+impl ReferencesRequest {
+    pub fn get_position(&self) -> &Position {
+        &self.params.text_document_position.position
+    }
+
+    pub fn get_document_uri(&self) -> &String {
+        &self.params.text_document_position.text_document.uri
+    }
+
+    pub fn include_declaration(&self) -> bool {
+        self.params.context.include_declaration
+    }
+
+    pub(crate) fn get_id(&self) -> &RequestId {
+        &self.base.id
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct ReferenceParams {
+    #[serde(flatten)]
+    text_document_position: TextDocumentPositionParams,
+    context: ReferenceContext,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct ReferenceContext {
+    include_declaration: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ReferencesResponse {
+    #[serde(flatten)]
+    base: ResponseMessageBase,
+    result: Option<Vec<Location>>,
+}
+
+impl ReferencesResponse {
+    pub fn new(id: &RequestId, locations: Option<Vec<Location>>) -> Self {
+        Self {
+            base: ResponseMessageBase::success(id),
+            result: locations,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::server::lsp::{
+        messages::{
+            textdocument_references::{ReferenceContext, ReferenceParams},
+            utils::TextDocumentPositionParams,
+        },
+        rpc::{Message, RequestId, RequestMessageBase},
+        textdocument::{Location, Position, Range, TextDocumentIdentifier},
+    };
+
+    use super::{ReferencesRequest, ReferencesResponse};
+
+    #[test]
+    fn deserialize() {
+        let message = br#"{"params":{"textDocument":{"uri":"file:///dings"},"position":{"character":7,"line":2},"context":{"includeDeclaration":true}},"method":"textDocument/references","id":3,"jsonrpc":"2.0"}"#;
+        let references_request: ReferencesRequest = serde_json::from_slice(message).unwrap();
+        assert_eq!(
+            references_request,
+            ReferencesRequest {
+                base: RequestMessageBase {
+                    base: Message {
+                        jsonrpc: "2.0".to_string(),
+                    },
+                    method: "textDocument/references".to_string(),
+                    id: RequestId::Integer(3)
+                },
+                params: ReferenceParams {
+                    text_document_position: TextDocumentPositionParams {
+                        text_document: TextDocumentIdentifier {
+                            uri: "file:///dings".to_string()
+                        },
+                        position: Position::new(2, 7)
+                    },
+                    context: ReferenceContext {
+                        include_declaration: true
+                    }
+                }
+            }
+        )
+    }
+
+    #[test]
+    fn serialize() {
+        let references_response = ReferencesResponse::new(
+            &RequestId::Integer(42),
+            Some(vec![Location::new("file:///dings", Range::new(0, 7, 0, 9))]),
+        );
+        assert_eq!(
+            serde_json::to_string(&references_response).unwrap(),
+            r#"{"jsonrpc":"2.0","id":42,"result":[{"uri":"file:///dings","range":{"start":{"line":0,"character":7},"end":{"line":0,"character":9}}}]}"#
+        );
+    }
+}
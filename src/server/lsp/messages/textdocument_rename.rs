@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+
+use crate::server::lsp::{
+    rpc::{RequestId, RequestMessageBase, ResponseMessageBase},
+    textdocument::{Position, Range},
+};
+
+use super::{utils::TextDocumentPositionParams, WorkspaceEdit};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct PrepareRenameRequest {
+    #[serde(flatten)]
+    base: RequestMessageBase,
+    params: PrepareRenameParams,
+}
+
+impl PrepareRenameRequest {
+    pub fn get_position(&self) -> &Position {
+        &self.params.text_document_position.position
+    }
+
+    pub fn get_document_uri(&self) -> &String {
+        &self.params.text_document_position.text_document.uri
+    }
+
+    pub(crate) fn get_id(&self) -> &RequestId {
+        &self.base.id
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct PrepareRenameParams {
+    #[serde(flatten)]
+    text_document_position: TextDocumentPositionParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct PrepareRenameResponse {
+    #[serde(flatten)]
+    base: ResponseMessageBase,
+    // NOTE: The result could also be `{ range, placeholder }` or `{ defaultBehavior }`.
+    result: Option<Range>,
+}
+
+impl PrepareRenameResponse {
+    pub fn new(id: &RequestId, range: Option<Range>) -> Self {
+        Self {
+            base: ResponseMessageBase::success(id),
+            result: range,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct RenameRequest {
+    #[serde(flatten)]
+    base: RequestMessageBase,
+    params: RenameParams,
+}
+
+impl RenameRequest {
+    pub fn get_position(&self) -> &Position {
+        &self.params.text_document_position.position
+    }
+
+    pub fn get_document_uri(&self) -> &String {
+        &self.params.text_document_position.text_document.uri
+    }
+
+    pub fn get_new_name(&self) -> &str {
+        &self.params.new_name
+    }
+
+    pub(crate) fn get_id(&self) -> &RequestId {
+        &self.base.id
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct RenameParams {
+    #[serde(flatten)]
+    text_document_position: TextDocumentPositionParams,
+    new_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct RenameResponse {
+    #[serde(flatten)]
+    base: ResponseMessageBase,
+    result: Option<WorkspaceEdit>,
+}
+
+impl RenameResponse {
+    pub fn new(id: &RequestId, edit: Option<WorkspaceEdit>) -> Self {
+        Self {
+            base: ResponseMessageBase::success(id),
+            result: edit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::server::lsp::{
+        messages::{
+            textdocument_rename::{PrepareRenameParams, RenameParams},
+            utils::TextDocumentPositionParams,
+        },
+        rpc::{Message, RequestId, RequestMessageBase},
+        textdocument::{Position, Range, TextDocumentIdentifier, TextEdit},
+        WorkspaceEdit,
+    };
+
+    use super::{PrepareRenameRequest, PrepareRenameResponse, RenameRequest, RenameResponse};
+
+    #[test]
+    fn deserialize_prepare_rename() {
+        let message = br#"{"params":{"textDocument":{"uri":"file:///dings"},"position":{"character":7,"line":2}},"method":"textDocument/prepareRename","id":3,"jsonrpc":"2.0"}"#;
+        let prepare_rename_request: PrepareRenameRequest = serde_json::from_slice(message).unwrap();
+        assert_eq!(
+            prepare_rename_request,
+            PrepareRenameRequest {
+                base: RequestMessageBase {
+                    base: Message {
+                        jsonrpc: "2.0".to_string(),
+                    },
+                    method: "textDocument/prepareRename".to_string(),
+                    id: RequestId::Integer(3)
+                },
+                params: PrepareRenameParams {
+                    text_document_position: TextDocumentPositionParams {
+                        text_document: TextDocumentIdentifier {
+                            uri: "file:///dings".to_string()
+                        },
+                        position: Position::new(2, 7)
+                    }
+                }
+            }
+        )
+    }
+
+    #[test]
+    fn serialize_prepare_rename() {
+        let prepare_rename_response =
+            PrepareRenameResponse::new(&RequestId::Integer(42), Some(Range::new(0, 8, 0, 12)));
+        assert_eq!(
+            serde_json::to_string(&prepare_rename_response).unwrap(),
+            r#"{"jsonrpc":"2.0","id":42,"result":{"start":{"line":0,"character":8},"end":{"line":0,"character":12}}}"#
+        );
+    }
+
+    #[test]
+    fn deserialize_rename() {
+        let message = br#"{"params":{"textDocument":{"uri":"file:///dings"},"position":{"character":7,"line":2},"newName":"person"},"method":"textDocument/rename","id":3,"jsonrpc":"2.0"}"#;
+        let rename_request: RenameRequest = serde_json::from_slice(message).unwrap();
+        assert_eq!(
+            rename_request,
+            RenameRequest {
+                base: RequestMessageBase {
+                    base: Message {
+                        jsonrpc: "2.0".to_string(),
+                    },
+                    method: "textDocument/rename".to_string(),
+                    id: RequestId::Integer(3)
+                },
+                params: RenameParams {
+                    text_document_position: TextDocumentPositionParams {
+                        text_document: TextDocumentIdentifier {
+                            uri: "file:///dings".to_string()
+                        },
+                        position: Position::new(2, 7)
+                    },
+                    new_name: "person".to_string()
+                }
+            }
+        )
+    }
+
+    #[test]
+    fn serialize_rename() {
+        let rename_response = RenameResponse::new(
+            &RequestId::Integer(42),
+            Some(WorkspaceEdit {
+                changes: HashMap::from([(
+                    "file:///dings".to_string(),
+                    vec![TextEdit::new(Range::new(0, 8, 0, 12), "person")],
+                )]),
+            }),
+        );
+        assert_eq!(
+            serde_json::to_string(&rename_response).unwrap(),
+            r#"{"jsonrpc":"2.0","id":42,"result":{"changes":{"file:///dings":[{"range":{"start":{"line":0,"character":8},"end":{"line":0,"character":12}},"newText":"person"}]}}}"#
+        );
+    }
+}
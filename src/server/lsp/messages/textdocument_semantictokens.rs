@@ -0,0 +1,197 @@
+use serde::{Deserialize, Serialize};
+
+use crate::server::lsp::{
+    rpc::{RequestId, RequestMessageBase, ResponseMessageBase},
+    textdocument::{Range, TextDocumentIdentifier},
+};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct SemanticTokensFullRequest {
+    #[serde(flatten)]
+    base: RequestMessageBase,
+    params: SemanticTokensParams,
+}
+
+impl SemanticTokensFullRequest {
+    pub fn get_document_uri(&self) -> &String {
+        &self.params.text_document.uri
+    }
+
+    pub(crate) fn get_id(&self) -> &RequestId {
+        &self.base.id
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct SemanticTokensParams {
+    text_document: TextDocumentIdentifier,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct SemanticTokensRangeRequest {
+    #[serde(flatten)]
+    base: RequestMessageBase,
+    params: SemanticTokensRangeParams,
+}
+
+impl SemanticTokensRangeRequest {
+    pub fn get_document_uri(&self) -> &String {
+        &self.params.text_document.uri
+    }
+
+    pub fn get_range(&self) -> &Range {
+        &self.params.range
+    }
+
+    pub(crate) fn get_id(&self) -> &RequestId {
+        &self.base.id
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct SemanticTokensRangeParams {
+    text_document: TextDocumentIdentifier,
+    range: Range,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct SemanticTokensResponse {
+    #[serde(flatten)]
+    base: ResponseMessageBase,
+    result: SemanticTokens,
+}
+
+impl SemanticTokensResponse {
+    pub fn new(id: &RequestId, data: Vec<u32>) -> Self {
+        Self {
+            base: ResponseMessageBase::success(id),
+            result: SemanticTokens { data },
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct SemanticTokens {
+    // NOTE: there is one more optional option:
+    // resultId: string
+    data: Vec<u32>,
+}
+
+/// The token types this server reports.
+/// The discriminant is the index into the legend announced in the server capabilities.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SemanticTokenType {
+    Keyword = 0,
+    Variable = 1,
+    Namespace = 2,
+    LocalName = 3,
+    Iri = 4,
+    String = 5,
+    Number = 6,
+    LanguageTag = 7,
+    Type = 8,
+    Function = 9,
+    BlankNode = 10,
+    Comment = 11,
+}
+
+impl SemanticTokenType {
+    pub const LEGEND: [&'static str; 12] = [
+        "keyword",
+        "variable",
+        "namespace",
+        "localName",
+        "iri",
+        "string",
+        "number",
+        "languageTag",
+        "type",
+        "function",
+        "blankNode",
+        "comment",
+    ];
+}
+
+/// The token modifiers this server reports.
+/// The discriminant is the bit of the modifier in the encoded modifier set.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SemanticTokenModifier {
+    Declaration = 0b01,
+    Unused = 0b10,
+}
+
+impl SemanticTokenModifier {
+    pub const LEGEND: [&'static str; 2] = ["declaration", "unused"];
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::server::lsp::{
+        messages::textdocument_semantictokens::{SemanticTokensParams, SemanticTokensRangeParams},
+        rpc::{Message, RequestId, RequestMessageBase},
+        textdocument::{Range, TextDocumentIdentifier},
+    };
+
+    use super::{SemanticTokensFullRequest, SemanticTokensRangeRequest, SemanticTokensResponse};
+
+    #[test]
+    fn deserialize_full() {
+        let message = br#"{"params":{"textDocument":{"uri":"file:///dings"}},"method":"textDocument/semanticTokens/full","id":3,"jsonrpc":"2.0"}"#;
+        let semantic_tokens_request: SemanticTokensFullRequest =
+            serde_json::from_slice(message).unwrap();
+        assert_eq!(
+            semantic_tokens_request,
+            SemanticTokensFullRequest {
+                base: RequestMessageBase {
+                    base: Message {
+                        jsonrpc: "2.0".to_string(),
+                    },
+                    method: "textDocument/semanticTokens/full".to_string(),
+                    id: RequestId::Integer(3)
+                },
+                params: SemanticTokensParams {
+                    text_document: TextDocumentIdentifier {
+                        uri: "file:///dings".to_string()
+                    }
+                }
+            }
+        )
+    }
+
+    #[test]
+    fn deserialize_range() {
+        let message = br#"{"params":{"textDocument":{"uri":"file:///dings"},"range":{"start":{"line":1,"character":0},"end":{"line":4,"character":2}}},"method":"textDocument/semanticTokens/range","id":3,"jsonrpc":"2.0"}"#;
+        let semantic_tokens_request: SemanticTokensRangeRequest =
+            serde_json::from_slice(message).unwrap();
+        assert_eq!(
+            semantic_tokens_request,
+            SemanticTokensRangeRequest {
+                base: RequestMessageBase {
+                    base: Message {
+                        jsonrpc: "2.0".to_string(),
+                    },
+                    method: "textDocument/semanticTokens/range".to_string(),
+                    id: RequestId::Integer(3)
+                },
+                params: SemanticTokensRangeParams {
+                    text_document: TextDocumentIdentifier {
+                        uri: "file:///dings".to_string()
+                    },
+                    range: Range::new(1, 0, 4, 2)
+                }
+            }
+        )
+    }
+
+    #[test]
+    fn serialize() {
+        let semantic_tokens_response =
+            SemanticTokensResponse::new(&RequestId::Integer(42), vec![0, 0, 6, 0, 0]);
+        assert_eq!(
+            serde_json::to_string(&semantic_tokens_response).unwrap(),
+            r#"{"jsonrpc":"2.0","id":42,"result":{"data":[0,0,6,0,0]}}"#
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+
+use crate::server::lsp::{
+    rpc::{RequestId, RequestMessageBase, ResponseMessageBase},
+    textdocument::Position,
+};
+
+use super::utils::TextDocumentPositionParams;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct SignatureHelpRequest {
+    #[serde(flatten)]
+    base: RequestMessageBase,
+    params: SignatureHelpParams,
+}
+
+impl SignatureHelpRequest {
+    pub fn get_position(&self) -> &Position {
+        &self.params.text_document_position.position
+    }
+
+    pub fn get_document_uri(&self) -> &String {
+        &self.params.text_document_position.text_document.uri
+    }
+
+    pub(crate) fn get_id(&self) -> &RequestId {
+        &self.base.id
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct SignatureHelpParams {
+    #[serde(flatten)]
+    text_document_position: TextDocumentPositionParams,
+    // NOTE: there are more optional options:
+    // context: SignatureHelpContext
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct SignatureHelpResponse {
+    #[serde(flatten)]
+    base: ResponseMessageBase,
+    result: Option<SignatureHelp>,
+}
+
+impl SignatureHelpResponse {
+    pub fn new(id: &RequestId, signature_help: Option<SignatureHelp>) -> Self {
+        Self {
+            base: ResponseMessageBase::success(id),
+            result: signature_help,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureHelp {
+    pub signatures: Vec<SignatureInformation>,
+    pub active_signature: u32,
+    pub active_parameter: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct SignatureInformation {
+    pub label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub documentation: Option<String>,
+    pub parameters: Vec<ParameterInformation>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ParameterInformation {
+    /// The start and end offset (in UTF-16 code units) of the parameter in the label
+    /// of the signature.
+    pub label: (u32, u32),
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub documentation: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::server::lsp::{
+        messages::utils::TextDocumentPositionParams,
+        rpc::{Message, RequestId, RequestMessageBase},
+        textdocument::{Position, TextDocumentIdentifier},
+    };
+
+    use super::{
+        ParameterInformation, SignatureHelp, SignatureHelpParams, SignatureHelpRequest,
+        SignatureHelpResponse, SignatureInformation,
+    };
+
+    #[test]
+    fn deserialize() {
+        let message = br#"{"params":{"textDocument":{"uri":"file:///dings"},"position":{"character":7,"line":2},"context":{"triggerKind":2,"triggerCharacter":"(","isRetrigger":false}},"method":"textDocument/signatureHelp","id":3,"jsonrpc":"2.0"}"#;
+        let signature_help_request: SignatureHelpRequest = serde_json::from_slice(message).unwrap();
+        assert_eq!(
+            signature_help_request,
+            SignatureHelpRequest {
+                base: RequestMessageBase {
+                    base: Message {
+                        jsonrpc: "2.0".to_string(),
+                    },
+                    method: "textDocument/signatureHelp".to_string(),
+                    id: RequestId::Integer(3)
+                },
+                params: SignatureHelpParams {
+                    text_document_position: TextDocumentPositionParams {
+                        text_document: TextDocumentIdentifier {
+                            uri: "file:///dings".to_string()
+                        },
+                        position: Position::new(2, 7)
+                    }
+                }
+            }
+        )
+    }
+
+    #[test]
+    fn serialize() {
+        let signature_help_response = SignatureHelpResponse::new(
+            &RequestId::Integer(42),
+            Some(SignatureHelp {
+                signatures: vec![SignatureInformation {
+                    label: "STRLEN(string) -> xsd:integer".to_string(),
+                    documentation: None,
+                    parameters: vec![ParameterInformation {
+                        label: (7, 13),
+                        documentation: None,
+                    }],
+                }],
+                active_signature: 0,
+                active_parameter: 0,
+            }),
+        );
+        assert_eq!(
+            serde_json::to_string(&signature_help_response).unwrap(),
+            r#"{"jsonrpc":"2.0","id":42,"result":{"signatures":[{"label":"STRLEN(string) -> xsd:integer","parameters":[{"label":[7,13]}]}],"activeSignature":0,"activeParameter":0}}"#
+        );
+        let empty_response = SignatureHelpResponse::new(&RequestId::Integer(42), None);
+        assert_eq!(
+            serde_json::to_string(&empty_response).unwrap(),
+            r#"{"jsonrpc":"2.0","id":42,"result":null}"#
+        );
+    }
+}
@@ -7,7 +7,7 @@ use std::{
 use log::error;
 use serde::{Deserialize, Serialize};
 
-use tree_sitter::{Node, Point};
+use tree_sitter::{InputEdit, Node, Point};
 
 use super::{
     errors::{ErrorCode, ResponseError},
@@ -35,7 +35,15 @@ impl TextDocumentItem {
         }
     }
 
-    fn apply_text_edit(&mut self, text_edit: TextEdit) {
+    pub(crate) fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub(crate) fn set_version(&mut self, version: u32) {
+        self.version = version;
+    }
+
+    pub(crate) fn apply_text_edit(&mut self, text_edit: TextEdit) {
         match text_edit.range.to_byte_index_range(&self.text) {
             Some(range) => {
                 self.text.replace_range(range, &text_edit.new_text);
@@ -97,6 +105,12 @@ pub struct VersionedTextDocumentIdentifier {
     version: u32,
 }
 
+impl VersionedTextDocumentIdentifier {
+    pub(crate) fn version(&self) -> u32 {
+        self.version
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct TextDocumentIdentifier {
     pub uri: Uri,
@@ -170,7 +184,7 @@ impl Position {
     ///   is undefined.
     /// * Ensure the provided UTF-16 position aligns with the logical structure of
     ///   the string.
-    pub fn to_byte_index(&self, text: &String) -> Option<usize> {
+    pub fn to_byte_index(&self, text: &str) -> Option<usize> {
         if self.line == 0 && self.character == 0 && text.is_empty() {
             return Some(0);
         }
@@ -191,7 +205,7 @@ impl Position {
     }
 
     /// Converts a UTF-8 based position to a UTF-16 based position.
-    pub fn translate_to_utf16_encoding(&mut self, text: &String) -> Result<(), ResponseError> {
+    pub fn translate_to_utf16_encoding(&mut self, text: &str) -> Result<(), ResponseError> {
         let line = text
             .lines()
             .chain(once(""))
@@ -297,19 +311,19 @@ impl Range {
         }
     }
 
-    #[cfg(test)]
     pub(crate) fn overlaps(&self, other: &Range) -> bool {
         self.start < other.end && self.end > other.start
     }
 
-    fn is_empty(&self) -> bool {
+    pub(crate) fn contains(&self, other: &Range) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
         self.start == self.end
     }
 
-    pub(crate) fn translate_to_utf16_encoding(
-        &mut self,
-        text: &String,
-    ) -> Result<(), ResponseError> {
+    pub(crate) fn translate_to_utf16_encoding(&mut self, text: &str) -> Result<(), ResponseError> {
         self.start.translate_to_utf16_encoding(text)?;
         self.end.translate_to_utf16_encoding(text)?;
         Ok(())
@@ -322,6 +336,22 @@ impl Display for Range {
     }
 }
 
+// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#location
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct Location {
+    pub uri: DocumentUri,
+    pub range: Range,
+}
+
+impl Location {
+    pub fn new(uri: &str, range: Range) -> Self {
+        Self {
+            uri: uri.to_string(),
+            range,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TextEdit {
@@ -355,6 +385,49 @@ impl TextEdit {
     pub(crate) fn is_empty(&self) -> bool {
         self.range.is_empty() && self.new_text.is_empty()
     }
+
+    /// Describes this edit, applied to `text`, as a tree-sitter `InputEdit`.
+    ///
+    /// Tree-sitter expects byte offsets and points with byte based columns,
+    /// while the range of a `TextEdit` is UTF-16 based.
+    /// Returns `None` if the range of this edit is not within `text`.
+    pub(crate) fn to_input_edit(&self, text: &String) -> Option<InputEdit> {
+        let byte_range = self.range.to_byte_index_range(text)?;
+        let start_position = byte_index_to_point(text, byte_range.start);
+        let new_end_position = match self.new_text.rfind('\n') {
+            Some(last_linebreak) => Point {
+                row: start_position.row + self.new_text.matches('\n').count(),
+                column: self.new_text.len() - last_linebreak - 1,
+            },
+            None => Point {
+                row: start_position.row,
+                column: start_position.column + self.new_text.len(),
+            },
+        };
+        Some(InputEdit {
+            start_byte: byte_range.start,
+            old_end_byte: byte_range.end,
+            new_end_byte: byte_range.start + self.new_text.len(),
+            start_position,
+            old_end_position: byte_index_to_point(text, byte_range.end),
+            new_end_position,
+        })
+    }
+}
+
+/// Converts a byte index into a tree-sitter `Point` (row and byte based column).
+pub(crate) fn byte_index_to_point(text: &str, byte_index: usize) -> Point {
+    let before = &text[..byte_index];
+    match before.rfind('\n') {
+        Some(last_linebreak) => Point {
+            row: before.matches('\n').count(),
+            column: byte_index - last_linebreak - 1,
+        },
+        None => Point {
+            row: 0,
+            column: byte_index,
+        },
+    }
 }
 
 impl Display for TextEdit {
@@ -371,6 +444,7 @@ impl Display for TextEdit {
 mod tests {
 
     use indoc::indoc;
+    use tree_sitter::{InputEdit, Point};
 
     use crate::server::lsp::textdocument::{Position, Range, TextEdit};
 
@@ -626,4 +700,35 @@ mod tests {
         assert!(!e.overlaps(&c));
         assert!(!e.overlaps(&d));
     }
+
+    #[test]
+    fn text_edit_to_input_edit() {
+        let text = "aä😀\nbc\n".to_string();
+        let edit = TextEdit::new(Range::new(0, 2, 1, 1), "x\nyz");
+        assert_eq!(
+            edit.to_input_edit(&text),
+            Some(InputEdit {
+                start_byte: 3,
+                old_end_byte: 9,
+                new_end_byte: 7,
+                start_position: Point { row: 0, column: 3 },
+                old_end_position: Point { row: 1, column: 1 },
+                new_end_position: Point { row: 1, column: 2 },
+            })
+        );
+        let edit = TextEdit::new(Range::new(1, 2, 1, 2), "ö");
+        assert_eq!(
+            edit.to_input_edit(&text),
+            Some(InputEdit {
+                start_byte: 10,
+                old_end_byte: 10,
+                new_end_byte: 12,
+                start_position: Point { row: 1, column: 2 },
+                old_end_position: Point { row: 1, column: 2 },
+                new_end_position: Point { row: 1, column: 4 },
+            })
+        );
+        let edit = TextEdit::new(Range::new(3, 0, 3, 0), "");
+        assert_eq!(edit.to_input_edit(&text), None);
+    }
 }
@@ -0,0 +1,536 @@
+//! The built-in calls of SPARQL 1.1 (see <https://www.w3.org/TR/sparql11-query/#SparqlOps>)
+//! and the aggregates (see <https://www.w3.org/TR/sparql11-query/#aggregates>).
+
+pub(super) enum ParameterKind {
+    Required,
+    Optional,
+    /// Can be repeated, e.g. the arguments of `CONCAT`.
+    Variadic,
+}
+
+pub(super) struct Parameter {
+    pub(super) name: &'static str,
+    pub(super) kind: ParameterKind,
+    pub(super) description: Option<&'static str>,
+}
+
+const fn required(name: &'static str) -> Parameter {
+    Parameter {
+        name,
+        kind: ParameterKind::Required,
+        description: None,
+    }
+}
+
+const fn optional(name: &'static str, description: &'static str) -> Parameter {
+    Parameter {
+        name,
+        kind: ParameterKind::Optional,
+        description: Some(description),
+    }
+}
+
+const fn variadic(name: &'static str) -> Parameter {
+    Parameter {
+        name,
+        kind: ParameterKind::Variadic,
+        description: None,
+    }
+}
+
+pub(super) struct BuiltinFunction {
+    /// The name as it is written in the grammar (and the kind of its token).
+    pub(super) name: &'static str,
+    pub(super) parameters: &'static [Parameter],
+    pub(super) returns: &'static str,
+    pub(super) description: &'static str,
+    pub(super) aggregate: bool,
+}
+
+const fn function(
+    name: &'static str,
+    parameters: &'static [Parameter],
+    returns: &'static str,
+    description: &'static str,
+) -> BuiltinFunction {
+    BuiltinFunction {
+        name,
+        parameters,
+        returns,
+        description,
+        aggregate: false,
+    }
+}
+
+const AGGREGATE_PARAMETERS: [Parameter; 1] = [required("expression")];
+
+const fn aggregate(
+    name: &'static str,
+    returns: &'static str,
+    description: &'static str,
+) -> BuiltinFunction {
+    BuiltinFunction {
+        name,
+        parameters: &AGGREGATE_PARAMETERS,
+        returns,
+        description,
+        aggregate: true,
+    }
+}
+
+impl BuiltinFunction {
+    /// The signature, e.g. `SUBSTR(source, start, [length]) -> string`.
+    pub(super) fn signature(&self) -> String {
+        format!(
+            "{}({}) -> {}",
+            self.name,
+            self.parameter_labels().join(", "),
+            self.returns
+        )
+    }
+
+    /// The labels of the parameters as they appear in the signature.
+    pub(super) fn parameter_labels(&self) -> Vec<String> {
+        self.parameters
+            .iter()
+            .map(|parameter| match parameter.kind {
+                ParameterKind::Required => parameter.name.to_string(),
+                ParameterKind::Optional => format!("[{}]", parameter.name),
+                ParameterKind::Variadic => format!("{}...", parameter.name),
+            })
+            .collect()
+    }
+
+    /// The anchor of the definition in the SPARQL 1.1 Query Language specification,
+    /// e.g. `func-strstarts` or `defn_aggGroupConcat`.
+    pub(super) fn specification_anchor(&self) -> String {
+        match self.name {
+            "COUNT" => "defn_aggCount",
+            "SUM" => "defn_aggSum",
+            "MIN" => "defn_aggMin",
+            "MAX" => "defn_aggMax",
+            "AVG" => "defn_aggAvg",
+            "SAMPLE" => "defn_aggSample",
+            "GROUP_CONCAT" => "defn_aggGroupConcat",
+            "isIRI" | "isURI" => "func-isIRI",
+            "isBLANK" => "func-isBlank",
+            "isLITERAL" => "func-isLiteral",
+            "isNUMERIC" => "func-isNumeric",
+            "sameTerm" => "func-sameTerm",
+            "LANGMATCHES" => "func-langMatches",
+            "URI" => "func-iri",
+            "ENCODE_FOR_URI" => "func-encode",
+            name => return format!("func-{}", name.to_lowercase()),
+        }
+        .to_string()
+    }
+
+    /// A snippet with a placeholder for each parameter that is not optional,
+    /// e.g. `SUBSTR(${1:source}, ${2:start})`.
+    pub(super) fn snippet(&self) -> String {
+        let placeholders: Vec<String> = self
+            .parameters
+            .iter()
+            .filter(|parameter| !matches!(parameter.kind, ParameterKind::Optional))
+            .enumerate()
+            .map(|(index, parameter)| format!("${{{}:{}}}", index + 1, parameter.name))
+            .collect();
+        format!("{}({})", self.name, placeholders.join(", "))
+    }
+}
+
+/// Finds a built-in function by its (case-insensitive) name.
+pub(super) fn find_builtin_function(name: &str) -> Option<&'static BuiltinFunction> {
+    BUILTIN_FUNCTIONS
+        .iter()
+        .find(|function| function.name.eq_ignore_ascii_case(name))
+}
+
+pub(super) const BUILTIN_FUNCTIONS: [BuiltinFunction; 59] = [
+    // Functional forms
+    function(
+        "BOUND",
+        &[required("variable")],
+        "xsd:boolean",
+        "Returns true if the variable is bound to a value.",
+    ),
+    function(
+        "IF",
+        &[
+            required("condition"),
+            required("then"),
+            required("else"),
+        ],
+        "term",
+        "Evaluates the condition and returns the value of `then` if it is true and the value of `else` otherwise.",
+    ),
+    function(
+        "COALESCE",
+        &[variadic("expression")],
+        "term",
+        "Returns the value of the first expression that evaluates without an error.",
+    ),
+    function(
+        "sameTerm",
+        &[required("term1"), required("term2")],
+        "xsd:boolean",
+        "Returns true if both terms are the same RDF term.",
+    ),
+    // Functions on RDF terms
+    function(
+        "isIRI",
+        &[required("term")],
+        "xsd:boolean",
+        "Returns true if the term is an IRI.",
+    ),
+    function(
+        "isURI",
+        &[required("term")],
+        "xsd:boolean",
+        "Returns true if the term is an IRI (alternative spelling of `isIRI`).",
+    ),
+    function(
+        "isBLANK",
+        &[required("term")],
+        "xsd:boolean",
+        "Returns true if the term is a blank node.",
+    ),
+    function(
+        "isLITERAL",
+        &[required("term")],
+        "xsd:boolean",
+        "Returns true if the term is a literal.",
+    ),
+    function(
+        "isNUMERIC",
+        &[required("term")],
+        "xsd:boolean",
+        "Returns true if the term is a numeric literal.",
+    ),
+    function(
+        "STR",
+        &[required("term")],
+        "simple literal",
+        "Returns the lexical form of a literal or the codepoint representation of an IRI.",
+    ),
+    function(
+        "LANG",
+        &[required("literal")],
+        "simple literal",
+        "Returns the language tag of the literal, or `\"\"` if it has none.",
+    ),
+    function(
+        "DATATYPE",
+        &[required("literal")],
+        "IRI",
+        "Returns the datatype IRI of the literal.",
+    ),
+    function(
+        "IRI",
+        &[required("string")],
+        "IRI",
+        "Creates an IRI from a string, relative IRIs are resolved against the base IRI.",
+    ),
+    function(
+        "URI",
+        &[required("string")],
+        "IRI",
+        "Creates an IRI from a string (alternative spelling of `IRI`).",
+    ),
+    function(
+        "BNODE",
+        &[optional(
+            "string",
+            "Optional, the same string yields the same blank node within one solution.",
+        )],
+        "blank node",
+        "Creates a fresh blank node. Calls with the same string in one solution return the same blank node.",
+    ),
+    function(
+        "STRDT",
+        &[required("lexicalForm"), required("datatype")],
+        "literal",
+        "Creates a literal with the lexical form and the datatype.",
+    ),
+    function(
+        "STRLANG",
+        &[required("lexicalForm"), required("languageTag")],
+        "literal",
+        "Creates a literal with the lexical form and the language tag.",
+    ),
+    function(
+        "UUID",
+        &[],
+        "IRI",
+        "Returns a fresh IRI from the UUID URN scheme.",
+    ),
+    function(
+        "STRUUID",
+        &[],
+        "simple literal",
+        "Returns a fresh UUID as string.",
+    ),
+    // Functions on strings
+    function(
+        "STRLEN",
+        &[required("string")],
+        "xsd:integer",
+        "Returns the number of characters of the string.",
+    ),
+    function(
+        "SUBSTR",
+        &[
+            required("source"),
+            required("start"),
+            optional("length", "Optional maximal number of characters, the rest of the string by default."),
+        ],
+        "string",
+        "Returns the substring of `source` that starts at position `start` (counting from 1) and has at most `length` characters.",
+    ),
+    function(
+        "UCASE",
+        &[required("string")],
+        "string",
+        "Converts the string to upper case.",
+    ),
+    function(
+        "LCASE",
+        &[required("string")],
+        "string",
+        "Converts the string to lower case.",
+    ),
+    function(
+        "STRSTARTS",
+        &[required("string"), required("prefix")],
+        "xsd:boolean",
+        "Returns true if the string starts with the prefix.",
+    ),
+    function(
+        "STRENDS",
+        &[required("string"), required("suffix")],
+        "xsd:boolean",
+        "Returns true if the string ends with the suffix.",
+    ),
+    function(
+        "CONTAINS",
+        &[required("string"), required("substring")],
+        "xsd:boolean",
+        "Returns true if the substring occurs in the string.",
+    ),
+    function(
+        "STRBEFORE",
+        &[required("string"), required("delimiter")],
+        "string",
+        "Returns the part of the string before the first occurrence of the delimiter.",
+    ),
+    function(
+        "STRAFTER",
+        &[required("string"), required("delimiter")],
+        "string",
+        "Returns the part of the string after the first occurrence of the delimiter.",
+    ),
+    function(
+        "ENCODE_FOR_URI",
+        &[required("string")],
+        "simple literal",
+        "Percent-encodes all characters of the string that are not allowed in a URI path segment.",
+    ),
+    function(
+        "CONCAT",
+        &[variadic("string")],
+        "string",
+        "Concatenates the strings.",
+    ),
+    function(
+        "LANGMATCHES",
+        &[required("languageTag"), required("languageRange")],
+        "xsd:boolean",
+        "Returns true if the language tag matches the language range (e.g. `\"en\"` or `\"*\"`).",
+    ),
+    function(
+        "REGEX",
+        &[
+            required("text"),
+            required("pattern"),
+            optional("flags", "Optional flags, e.g. `\"i\"` for case-insensitive matching."),
+        ],
+        "xsd:boolean",
+        "Returns true if the text matches the regular expression. The flags (e.g. `\"i\"`) modify the matching.",
+    ),
+    function(
+        "REPLACE",
+        &[
+            required("text"),
+            required("pattern"),
+            required("replacement"),
+            optional("flags", "Optional flags, e.g. `\"i\"` for case-insensitive matching."),
+        ],
+        "string",
+        "Replaces each match of the regular expression in the text with the replacement.",
+    ),
+    // Functions on numerics
+    function(
+        "ABS",
+        &[required("number")],
+        "numeric",
+        "Returns the absolute value of the number.",
+    ),
+    function(
+        "ROUND",
+        &[required("number")],
+        "numeric",
+        "Rounds the number to the nearest integer, halves are rounded towards positive infinity.",
+    ),
+    function(
+        "CEIL",
+        &[required("number")],
+        "numeric",
+        "Returns the smallest integer that is not smaller than the number.",
+    ),
+    function(
+        "FLOOR",
+        &[required("number")],
+        "numeric",
+        "Returns the largest integer that is not larger than the number.",
+    ),
+    function(
+        "RAND",
+        &[],
+        "xsd:double",
+        "Returns a random number between 0 (inclusive) and 1 (exclusive).",
+    ),
+    // Functions on dates and times
+    function(
+        "NOW",
+        &[],
+        "xsd:dateTime",
+        "Returns the time of the query execution, the same value for every call in a query.",
+    ),
+    function(
+        "YEAR",
+        &[required("dateTime")],
+        "xsd:integer",
+        "Returns the year of the date.",
+    ),
+    function(
+        "MONTH",
+        &[required("dateTime")],
+        "xsd:integer",
+        "Returns the month of the date.",
+    ),
+    function(
+        "DAY",
+        &[required("dateTime")],
+        "xsd:integer",
+        "Returns the day of the date.",
+    ),
+    function(
+        "HOURS",
+        &[required("dateTime")],
+        "xsd:integer",
+        "Returns the hours of the time.",
+    ),
+    function(
+        "MINUTES",
+        &[required("dateTime")],
+        "xsd:integer",
+        "Returns the minutes of the time.",
+    ),
+    function(
+        "SECONDS",
+        &[required("dateTime")],
+        "xsd:decimal",
+        "Returns the seconds of the time.",
+    ),
+    function(
+        "TIMEZONE",
+        &[required("dateTime")],
+        "xsd:dayTimeDuration",
+        "Returns the timezone of the date as duration.",
+    ),
+    function(
+        "TZ",
+        &[required("dateTime")],
+        "simple literal",
+        "Returns the timezone of the date as string, e.g. `\"Z\"` or `\"-05:00\"`.",
+    ),
+    // Hash functions
+    function(
+        "MD5",
+        &[required("string")],
+        "simple literal",
+        "Returns the MD5 checksum of the string as hex digits.",
+    ),
+    function(
+        "SHA1",
+        &[required("string")],
+        "simple literal",
+        "Returns the SHA1 checksum of the string as hex digits.",
+    ),
+    function(
+        "SHA256",
+        &[required("string")],
+        "simple literal",
+        "Returns the SHA256 checksum of the string as hex digits.",
+    ),
+    function(
+        "SHA384",
+        &[required("string")],
+        "simple literal",
+        "Returns the SHA384 checksum of the string as hex digits.",
+    ),
+    function(
+        "SHA512",
+        &[required("string")],
+        "simple literal",
+        "Returns the SHA512 checksum of the string as hex digits.",
+    ),
+    // Aggregates
+    aggregate(
+        "COUNT",
+        "xsd:integer",
+        "Counts the values of the group, `COUNT(*)` counts the solutions.",
+    ),
+    aggregate("SUM", "numeric", "Sums up the values of the group."),
+    aggregate("MIN", "term", "Returns the smallest value of the group."),
+    aggregate("MAX", "term", "Returns the largest value of the group."),
+    aggregate("AVG", "numeric", "Returns the average of the values of the group."),
+    aggregate("SAMPLE", "term", "Returns an arbitrary value of the group."),
+    aggregate(
+        "GROUP_CONCAT",
+        "string",
+        "Concatenates the values of the group, separated by the `SEPARATOR` (a space by default).",
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{find_builtin_function, BUILTIN_FUNCTIONS};
+
+    #[test]
+    fn signature() {
+        let substr = find_builtin_function("substr").unwrap();
+        assert_eq!(
+            substr.signature(),
+            "SUBSTR(source, start, [length]) -> string"
+        );
+        assert_eq!(substr.snippet(), "SUBSTR(${1:source}, ${2:start})");
+        let concat = find_builtin_function("CONCAT").unwrap();
+        assert_eq!(concat.signature(), "CONCAT(string...) -> string");
+        assert_eq!(concat.snippet(), "CONCAT(${1:string})");
+        assert_eq!(find_builtin_function("NOW").unwrap().snippet(), "NOW()");
+    }
+
+    #[test]
+    fn names_are_unique() {
+        for (index, function) in BUILTIN_FUNCTIONS.iter().enumerate() {
+            assert!(
+                BUILTIN_FUNCTIONS[..index]
+                    .iter()
+                    .all(|other| other.name != function.name),
+                "\"{}\" occurs twice",
+                function.name
+            );
+        }
+    }
+}
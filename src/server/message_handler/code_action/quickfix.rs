@@ -25,6 +25,7 @@ pub(super) fn get_quickfix(
         Some(DiagnosticCode::String(ref diagnostic_code)) => match diagnostic_code.as_str() {
             "undeclared-prefix" => declare_prefix(server, document_uri, diagnostic),
             "uncompacted-uri" => shorten_uri(server, document_uri, diagnostic),
+            "unbound-variable" => replace_variable(document_uri, diagnostic),
             _ => {
                 log::warn!("Unknown diagnostic code: {}", diagnostic_code);
                 Ok(None)
@@ -98,6 +99,33 @@ fn declare_prefix(
     }
 }
 
+/// Replaces a variable that is never bound with the bound variable of the most similar name,
+/// if the diagnostic found one.
+fn replace_variable(
+    document_uri: &String,
+    diagnostic: Diagnostic,
+) -> Result<Option<CodeAction>, ResponseError> {
+    match &diagnostic.data {
+        Some(LSPAny::String(variable)) => {
+            let mut code_action = CodeAction::new(
+                &format!("Replace with \"{}\"", variable),
+                Some(CodeActionKind::QuickFix),
+            );
+            code_action.add_edit(
+                document_uri,
+                TextEdit::new(diagnostic.range.clone(), variable),
+            );
+            code_action.diagnostics.push(diagnostic);
+            Ok(Some(code_action))
+        }
+        Some(_) => Err(ResponseError::new(
+            ErrorCode::InvalidParams,
+            "expected variable in unbound-variable data",
+        )),
+        None => Ok(None),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use indoc::indoc;
@@ -110,7 +138,7 @@ mod test {
             diagnostic::{self, Diagnostic},
             textdocument::{Range, TextDocumentItem, TextEdit},
         },
-        message_handler::code_action::quickfix::shorten_uri,
+        message_handler::code_action::quickfix::{replace_variable, shorten_uri},
         state::ServerState,
         Server,
     };
@@ -197,4 +225,37 @@ mod test {
             &vec![TextEdit::new(Range::new(2, 5, 2, 29), "schema:name"),]
         );
     }
+
+    #[test]
+    fn replace_unbound_variable() {
+        let diagnostic = Diagnostic {
+            range: Range::new(0, 7, 0, 12),
+            severity: diagnostic::DiagnosticSeverity::Warning,
+            message: String::new(),
+            source: None,
+            code: None,
+            data: Some(LSPAny::String("?name".to_string())),
+        };
+
+        let code_action = replace_variable(&"uri".to_string(), diagnostic)
+            .unwrap()
+            .unwrap();
+        assert_eq!(code_action.title, "Replace with \"?name\"");
+        assert_eq!(
+            code_action.edit.changes.get("uri").unwrap(),
+            &vec![TextEdit::new(Range::new(0, 7, 0, 12), "?name")]
+        );
+
+        let diagnostic = Diagnostic {
+            range: Range::new(0, 7, 0, 9),
+            severity: diagnostic::DiagnosticSeverity::Warning,
+            message: String::new(),
+            source: None,
+            code: None,
+            data: None,
+        };
+        assert!(replace_variable(&"uri".to_string(), diagnostic)
+            .unwrap()
+            .is_none());
+    }
 }
@@ -1,15 +1,13 @@
-use log::error;
+use log::{error, info};
 
 use crate::server::{
     common::{serde_parse, PublishDiagnosticsCommandAruments},
+    configuration::Settings,
     lsp::{
         errors::{ErrorCode, ResponseError},
-        rpc::NotificationMessageBase,
-        ExecuteCommandRequest, ExecuteCommandResponse, PublishDiagnosticsNotification,
-        PublishDiagnosticsPrarams,
+        ExecuteCommandRequest, ExecuteCommandResponse,
     },
-    message_handler::collect_diagnostics,
-    state::ServerStatus,
+    message_handler::publish_diagnostics,
     Server,
 };
 
@@ -23,6 +21,14 @@ pub fn handle_execute_command_request(
             publish_diagnostic(server, &arguments);
             Ok(ExecuteCommandResponse::new(request.get_id()))
         }
+        "reloadPrefixes" => {
+            reload_prefixes(server);
+            Ok(ExecuteCommandResponse::new(request.get_id()))
+        }
+        "reloadVocabularies" => {
+            reload_vocabularies(server);
+            Ok(ExecuteCommandResponse::new(request.get_id()))
+        }
         unknown_command => {
             error!("Received unknown Command request: {}", unknown_command);
             Err(ResponseError::new(
@@ -34,26 +40,28 @@ pub fn handle_execute_command_request(
 }
 
 fn publish_diagnostic(server: &Server, args: &PublishDiagnosticsCommandAruments) {
-    let uri = &args.0 .0;
-    if server.state.status == ServerStatus::Running {
-        match collect_diagnostics(server, uri) {
-            Ok(diagnostics) => {
-                let diagnostic_notification = PublishDiagnosticsNotification {
-                    base: NotificationMessageBase::new("textDocument/publishDiagnistics"),
-                    params: PublishDiagnosticsPrarams {
-                        uri: uri.to_string(),
-                        diagnostics: diagnostics.collect(),
-                    },
-                };
-                let message = serde_json::to_string(&diagnostic_notification).unwrap();
-                server.send_message(message);
-            }
-            Err(error) => {
-                error!(
-                    "Error occured while publishing diagnostics:\n\n{}",
-                    error.message
-                )
-            }
-        }
-    }
+    publish_diagnostics(server, &args.0 .0);
+}
+
+/// Rereads the prefix sources from the configuration and rebuilds the prefix registry.
+fn reload_prefixes(server: &mut Server) {
+    let settings = Settings::new();
+    server.settings.builtin_prefixes = settings.builtin_prefixes;
+    server.settings.prefix_files = settings.prefix_files;
+    server.settings.prefixes = settings.prefixes;
+    server.tools.load_prefixes(&server.settings);
+    info!(
+        "Reloaded prefix registry ({} prefixes)",
+        server.tools.uri_converter.len()
+    );
+}
+
+/// Rereads the vocabulary files from the configuration and indexes them again.
+fn reload_vocabularies(server: &mut Server) {
+    server.settings.vocabulary_files = Settings::new().vocabulary_files;
+    server.tools.load_vocabulary(&server.settings);
+    info!(
+        "Reloaded vocabularies ({} terms)",
+        server.tools.vocabulary.len()
+    );
 }
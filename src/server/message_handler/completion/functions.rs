@@ -0,0 +1,35 @@
+use crate::server::{
+    lsp::{CompletionItem, CompletionItemKind, InsertTextFormat, MarkupContent},
+    message_handler::builtins::{BuiltinFunction, BUILTIN_FUNCTIONS},
+};
+
+use super::probe::CompletionProbe;
+
+fn to_completion_item(function: &BuiltinFunction) -> CompletionItem {
+    let mut item = CompletionItem::new(
+        function.name,
+        &function.signature(),
+        &function.snippet(),
+        CompletionItemKind::Function,
+        InsertTextFormat::Snippet,
+    );
+    item.documentation = Some(MarkupContent::markdown(function.description));
+    item
+}
+
+/// The built-in functions, if the grammar accepts an expression at the cursor.
+///
+/// NOTE: All built-in calls (and all aggregates) are valid in the same positions,
+/// so one probe per group suffices.
+pub(super) fn function_completions(probe: &mut CompletionProbe) -> Vec<CompletionItem> {
+    let functions = probe.accepts("STR(?v)", "STR");
+    let aggregates = probe.accepts("COUNT(?v)", "COUNT");
+    BUILTIN_FUNCTIONS
+        .iter()
+        .filter(|function| match function.aggregate {
+            true => aggregates,
+            false => functions,
+        })
+        .map(to_completion_item)
+        .collect()
+}
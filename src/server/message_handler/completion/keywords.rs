@@ -0,0 +1,265 @@
+use crate::server::lsp::{CompletionItem, CompletionItemKind, InsertTextFormat};
+
+use super::probe::CompletionProbe;
+
+struct KeywordCompletion {
+    label: &'static str,
+    detail: &'static str,
+    snippet: &'static str,
+    /// Minimal valid texts starting with the keyword, used to test if the keyword
+    /// is valid at the cursor (see `CompletionProbe`).
+    probes: &'static [&'static str],
+}
+
+impl KeywordCompletion {
+    fn to_completion_item(&self) -> CompletionItem {
+        CompletionItem::new(
+            self.label,
+            self.detail,
+            self.snippet,
+            CompletionItemKind::Keyword,
+            InsertTextFormat::Snippet,
+        )
+    }
+}
+
+const KEYWORD_COMPLETIONS: [KeywordCompletion; 36] = [
+    // Prologue
+    KeywordCompletion {
+        label: "PREFIX",
+        detail: "Declare a namespace",
+        snippet: "PREFIX ${1:namespace}: <${0:iri}>",
+        probes: &["PREFIX p: <a>", "PREFIX p: <a> ASK {}"],
+    },
+    KeywordCompletion {
+        label: "BASE",
+        detail: "Set the Base URI",
+        snippet: "BASE <${0}>",
+        probes: &["BASE <a>", "BASE <a> ASK {}"],
+    },
+    // Query forms
+    KeywordCompletion {
+        label: "SELECT",
+        detail: "Select query",
+        snippet: "SELECT ${1:*} WHERE {\n  $0\n}",
+        probes: &["SELECT * WHERE {}"],
+    },
+    KeywordCompletion {
+        label: "CONSTRUCT",
+        detail: "Construct query",
+        snippet: "CONSTRUCT {\n  $1\n}\nWHERE {\n  $0\n}",
+        probes: &["CONSTRUCT { ?s ?p ?o } WHERE {}"],
+    },
+    KeywordCompletion {
+        label: "DESCRIBE",
+        detail: "Describe query",
+        snippet: "DESCRIBE $0",
+        probes: &["DESCRIBE ?v"],
+    },
+    KeywordCompletion {
+        label: "ASK",
+        detail: "Ask query",
+        snippet: "ASK {\n  $0\n}",
+        probes: &["ASK {}"],
+    },
+    // Select clause and dataset
+    KeywordCompletion {
+        label: "DISTINCT",
+        detail: "Remove duplicate results",
+        snippet: "DISTINCT",
+        probes: &["DISTINCT", "DISTINCT *"],
+    },
+    KeywordCompletion {
+        label: "REDUCED",
+        detail: "Permit elimination of duplicate results",
+        snippet: "REDUCED",
+        probes: &["REDUCED *"],
+    },
+    KeywordCompletion {
+        label: "AS",
+        detail: "Name the result of an expression",
+        snippet: "AS ?$0",
+        probes: &["AS ?v"],
+    },
+    KeywordCompletion {
+        label: "FROM",
+        detail: "Add a graph to the default graph",
+        snippet: "FROM <$0>",
+        probes: &["FROM <a>"],
+    },
+    KeywordCompletion {
+        label: "FROM NAMED",
+        detail: "Add a named graph",
+        snippet: "FROM NAMED <$0>",
+        probes: &["FROM NAMED <a>"],
+    },
+    KeywordCompletion {
+        label: "WHERE",
+        detail: "Graph pattern to match",
+        snippet: "WHERE {\n  $0\n}",
+        probes: &["WHERE {}"],
+    },
+    // Solution modifiers
+    KeywordCompletion {
+        label: "GROUP BY",
+        detail: "Group the results",
+        snippet: "GROUP BY $0",
+        probes: &["GROUP BY ?v"],
+    },
+    KeywordCompletion {
+        label: "HAVING",
+        detail: "Filter the groups",
+        snippet: "HAVING ($0)",
+        probes: &["HAVING (?v)"],
+    },
+    KeywordCompletion {
+        label: "ORDER BY",
+        detail: "Sort the results",
+        snippet: "ORDER BY ${1|ASC,DESC|} ( $0 )",
+        probes: &["ORDER BY ?v"],
+    },
+    KeywordCompletion {
+        label: "ASC",
+        detail: "Sort ascending",
+        snippet: "ASC($0)",
+        probes: &["ASC(?v)"],
+    },
+    KeywordCompletion {
+        label: "DESC",
+        detail: "Sort descending",
+        snippet: "DESC($0)",
+        probes: &["DESC(?v)"],
+    },
+    KeywordCompletion {
+        label: "LIMIT",
+        detail: "Limit the number of results",
+        snippet: "LIMIT ${0:100}",
+        probes: &["LIMIT 1"],
+    },
+    KeywordCompletion {
+        label: "OFFSET",
+        detail: "Skip results",
+        snippet: "OFFSET ${0:100}",
+        probes: &["OFFSET 1"],
+    },
+    // Graph patterns
+    KeywordCompletion {
+        label: "FILTER",
+        detail: "Filter the results",
+        snippet: "FILTER ( $0 )",
+        probes: &["FILTER (?v)"],
+    },
+    KeywordCompletion {
+        label: "BIND",
+        detail: "Bind a new variable",
+        snippet: "BIND ($1 AS ?$0)",
+        probes: &["BIND (?v AS ?w)"],
+    },
+    KeywordCompletion {
+        label: "VALUES",
+        detail: "Inline data definition",
+        snippet: "VALUES ?$1 { $0 }",
+        probes: &["VALUES ?v {}"],
+    },
+    KeywordCompletion {
+        label: "SERVICE",
+        detail: "Collect data from a fedarated SPARQL endpoint",
+        snippet: "SERVICE <$1> {\n  $0\n}",
+        probes: &["SERVICE <a> {}"],
+    },
+    KeywordCompletion {
+        label: "MINUS",
+        detail: "Subtract data",
+        snippet: "MINUS { $0 }",
+        probes: &["MINUS {}"],
+    },
+    KeywordCompletion {
+        label: "OPTIONAL",
+        detail: "Optional graphpattern",
+        snippet: "OPTIONAL { $0 }",
+        probes: &["OPTIONAL {}"],
+    },
+    KeywordCompletion {
+        label: "UNION",
+        detail: "Union of two results",
+        snippet: "UNION {\n  $0\n}",
+        probes: &["UNION {}"],
+    },
+    KeywordCompletion {
+        label: "GRAPH",
+        detail: "Match a named graph",
+        snippet: "GRAPH ${1:?graph} {\n  $0\n}",
+        probes: &["GRAPH ?v {}"],
+    },
+    KeywordCompletion {
+        label: "UNDEF",
+        detail: "Unbound value",
+        snippet: "UNDEF",
+        probes: &["UNDEF"],
+    },
+    // Expressions
+    KeywordCompletion {
+        label: "IN",
+        detail: "Test if a value is in a list",
+        snippet: "IN ($0)",
+        probes: &["IN (?v)"],
+    },
+    KeywordCompletion {
+        label: "NOT IN",
+        detail: "Test if a value is not in a list",
+        snippet: "NOT IN ($0)",
+        probes: &["NOT IN (?v)"],
+    },
+    KeywordCompletion {
+        label: "SEPARATOR",
+        detail: "Separator of the concatenated values",
+        snippet: "SEPARATOR=\"$0\"",
+        probes: &["SEPARATOR = \"\""],
+    },
+    // Updates
+    KeywordCompletion {
+        label: "INSERT DATA",
+        detail: "Insert triples",
+        snippet: "INSERT DATA {\n  $0\n}",
+        probes: &["INSERT DATA {}"],
+    },
+    KeywordCompletion {
+        label: "DELETE DATA",
+        detail: "Delete triples",
+        snippet: "DELETE DATA {\n  $0\n}",
+        probes: &["DELETE DATA {}"],
+    },
+    KeywordCompletion {
+        label: "DELETE WHERE",
+        detail: "Delete the matching triples",
+        snippet: "DELETE WHERE {\n  $0\n}",
+        probes: &["DELETE WHERE {}"],
+    },
+    KeywordCompletion {
+        label: "INSERT",
+        detail: "Insert triples for each match",
+        snippet: "INSERT {\n  $1\n}\nWHERE {\n  $0\n}",
+        probes: &["INSERT {} WHERE {}"],
+    },
+    KeywordCompletion {
+        label: "DELETE",
+        detail: "Delete triples for each match",
+        snippet: "DELETE {\n  $1\n}\nWHERE {\n  $0\n}",
+        probes: &["DELETE {} WHERE {}"],
+    },
+];
+
+/// The keywords that the grammar accepts at the cursor.
+pub(super) fn keyword_completions(probe: &mut CompletionProbe) -> Vec<CompletionItem> {
+    KEYWORD_COMPLETIONS
+        .iter()
+        .filter(|keyword| {
+            let token = keyword.label.split(' ').next().unwrap_or(keyword.label);
+            keyword
+                .probes
+                .iter()
+                .any(|candidate| probe.accepts(candidate, token))
+        })
+        .map(KeywordCompletion::to_completion_item)
+        .collect()
+}
@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+
+use tree_sitter::{Node, Tree};
+
+use crate::server::{
+    anaysis::{compact_with_declared_prefix, get_prefix_map},
+    lsp::{
+        textdocument::{byte_index_to_point, Range, TextEdit},
+        CompletionItem, CompletionItemKind, InsertTextFormat,
+    },
+};
+
+use super::{
+    prefixes::{declaration_edit, find_prologue, prefix_declarations},
+    probe::CompletionProbe,
+};
+
+const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+const RDF: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+
+/// Common languages: the BCP-47 tag (the shortest ISO 639 code), the ISO 639-2 code, that is
+/// often mistaken for the tag (e.g. `@eng`), and the name.
+const LANGUAGES: [(&str, &str, &str); 48] = [
+    ("ar", "ara", "Arabic"),
+    ("bg", "bul", "Bulgarian"),
+    ("bn", "ben", "Bengali"),
+    ("ca", "cat", "Catalan"),
+    ("cs", "ces", "Czech"),
+    ("cy", "cym", "Welsh"),
+    ("da", "dan", "Danish"),
+    ("de", "deu", "German"),
+    ("el", "ell", "Greek"),
+    ("en", "eng", "English"),
+    ("en-GB", "eng", "English (United Kingdom)"),
+    ("en-US", "eng", "English (United States)"),
+    ("eo", "epo", "Esperanto"),
+    ("es", "spa", "Spanish"),
+    ("et", "est", "Estonian"),
+    ("eu", "eus", "Basque"),
+    ("fa", "fas", "Persian"),
+    ("fi", "fin", "Finnish"),
+    ("fr", "fra", "French"),
+    ("ga", "gle", "Irish"),
+    ("he", "heb", "Hebrew"),
+    ("hi", "hin", "Hindi"),
+    ("hr", "hrv", "Croatian"),
+    ("hu", "hun", "Hungarian"),
+    ("id", "ind", "Indonesian"),
+    ("is", "isl", "Icelandic"),
+    ("it", "ita", "Italian"),
+    ("ja", "jpn", "Japanese"),
+    ("ko", "kor", "Korean"),
+    ("la", "lat", "Latin"),
+    ("lt", "lit", "Lithuanian"),
+    ("lv", "lav", "Latvian"),
+    ("nb", "nob", "Norwegian Bokmål"),
+    ("nl", "nld", "Dutch"),
+    ("pl", "pol", "Polish"),
+    ("pt", "por", "Portuguese"),
+    ("pt-BR", "por", "Portuguese (Brazil)"),
+    ("ro", "ron", "Romanian"),
+    ("ru", "rus", "Russian"),
+    ("sk", "slk", "Slovak"),
+    ("sl", "slv", "Slovenian"),
+    ("sr", "srp", "Serbian"),
+    ("sv", "swe", "Swedish"),
+    ("th", "tha", "Thai"),
+    ("tr", "tur", "Turkish"),
+    ("uk", "ukr", "Ukrainian"),
+    ("vi", "vie", "Vietnamese"),
+    ("zh", "zho", "Chinese"),
+];
+
+/// The datatypes of literals: namespace, local name and description.
+const DATATYPES: [(&str, &str, &str); 29] = [
+    (XSD, "string", "Character string"),
+    (XSD, "boolean", "true or false"),
+    (XSD, "decimal", "Arbitrary precision decimal number"),
+    (XSD, "integer", "Arbitrary size integer"),
+    (XSD, "double", "64-bit floating point number"),
+    (XSD, "float", "32-bit floating point number"),
+    (XSD, "date", "Date, e.g. 2024-12-31"),
+    (XSD, "time", "Time of day, e.g. 23:59:59"),
+    (XSD, "dateTime", "Date and time, e.g. 2024-12-31T23:59:59"),
+    (XSD, "dateTimeStamp", "Date and time with required timezone"),
+    (XSD, "duration", "Duration, e.g. P1Y2M3DT4H"),
+    (
+        XSD,
+        "dayTimeDuration",
+        "Duration in days, hours, minutes and seconds",
+    ),
+    (XSD, "yearMonthDuration", "Duration in years and months"),
+    (XSD, "gYear", "Gregorian year, e.g. 2024"),
+    (XSD, "gYearMonth", "Gregorian year and month, e.g. 2024-12"),
+    (XSD, "long", "64-bit integer"),
+    (XSD, "int", "32-bit integer"),
+    (XSD, "short", "16-bit integer"),
+    (XSD, "byte", "8-bit integer"),
+    (XSD, "nonNegativeInteger", "Integer ≥ 0"),
+    (XSD, "positiveInteger", "Integer > 0"),
+    (XSD, "nonPositiveInteger", "Integer ≤ 0"),
+    (XSD, "negativeInteger", "Integer < 0"),
+    (XSD, "anyURI", "URI reference"),
+    (XSD, "language", "Language tag, e.g. en-US"),
+    (XSD, "hexBinary", "Hex-encoded binary data"),
+    (XSD, "base64Binary", "Base64-encoded binary data"),
+    (RDF, "langString", "Language-tagged string"),
+    (RDF, "HTML", "HTML fragment"),
+];
+
+/// The usual prefix of a datatype namespace.
+fn usual_prefix(namespace: &str) -> &'static str {
+    match namespace {
+        XSD => "xsd",
+        _ => "rdf",
+    }
+}
+
+/// The range between the byte indices `start` and `end`, in the UTF-16 columns of LSP.
+fn range(text: &str, start: usize, end: usize) -> Option<Range> {
+    let mut range = Range::from_ts_positions(
+        byte_index_to_point(text, start),
+        byte_index_to_point(text, end),
+    );
+    range.translate_to_utf16_encoding(text).ok()?;
+    Some(range)
+}
+
+/// The byte index of the `@` of the language tag that ends at the cursor,
+/// if the grammar accepts a language tag there, e.g. `"Berlin"@de-|`.
+pub(super) fn language_tag_start(tree: &Tree, text: &str, cursor: usize) -> Option<usize> {
+    let at = text
+        .get(..cursor)?
+        .char_indices()
+        .rev()
+        .find(|(_, char)| !char.is_ascii_alphanumeric() && *char != '-')
+        .filter(|(_, char)| *char == '@')?
+        .0;
+    CompletionProbe::with_word_start(tree, text, at, cursor)?
+        .accepts("@en", "LANGTAG")
+        .then_some(at)
+}
+
+/// Counts the language tags of the document, besides the one at `at` that is being typed.
+fn collect_language_tags<'a>(
+    node: Node,
+    text: &'a str,
+    at: usize,
+    tags: &mut HashMap<&'a str, usize>,
+) {
+    if node.kind() == "LANGTAG" && node.start_byte() != at {
+        if let Ok(tag) = node.utf8_text(text.as_bytes()) {
+            *tags.entry(&tag[1..]).or_default() += 1;
+        }
+    }
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.children(&mut cursor).collect();
+    for child in children {
+        collect_language_tags(child, text, at, tags);
+    }
+}
+
+/// Checks if the primary subtag of `tag` is the ISO 639-2 code of a language with a shorter
+/// tag, like `eng` for `en`.
+fn is_misspelled(tag: &str) -> bool {
+    let primary = tag.split('-').next().unwrap_or(tag);
+    LANGUAGES
+        .iter()
+        .any(|(_, code, _)| code.eq_ignore_ascii_case(primary))
+}
+
+/// Language tags for the literal whose `@` is at `at`.
+///
+/// The tags used in the document come first, the most used first.
+/// Misspelled tags like `@eng` are not suggested, but typing them finds the right tag.
+pub(super) fn language_tag_completions(
+    tree: &Tree,
+    text: &str,
+    at: usize,
+    cursor: usize,
+) -> Vec<CompletionItem> {
+    let mut used = HashMap::new();
+    collect_language_tags(tree.root_node(), text, at, &mut used);
+    let mut used: Vec<(&str, usize)> = used
+        .into_iter()
+        .filter(|(tag, _)| !tag.is_empty() && !is_misspelled(tag))
+        .collect();
+    used.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+    let rank = |tag: &str| {
+        used.iter()
+            .position(|(used, _)| used.eq_ignore_ascii_case(tag))
+    };
+    let Some(edit_range) = range(text, at + 1, cursor) else {
+        return vec![];
+    };
+    let item = |tag: &str, detail: &str, filter_text: String| {
+        let mut item = CompletionItem::new(
+            tag,
+            detail,
+            tag,
+            CompletionItemKind::Constant,
+            InsertTextFormat::PlainText,
+        );
+        item.sort_text = Some(match rank(tag) {
+            Some(rank) => format!("0{:04}", rank),
+            None => format!("1{}", tag.to_lowercase()),
+        });
+        item.filter_text = Some(filter_text);
+        item.text_edit = Some(TextEdit::new(edit_range.clone(), tag));
+        item
+    };
+    let mut items: Vec<CompletionItem> = LANGUAGES
+        .iter()
+        .map(|(tag, code, name)| item(tag, name, format!("{} {} {}", tag, code, name)))
+        .chain(
+            used.iter()
+                .filter(|(tag, _)| {
+                    !LANGUAGES
+                        .iter()
+                        .any(|(known, _, _)| known.eq_ignore_ascii_case(tag))
+                })
+                .map(|(tag, _)| item(tag, "Used in this document", tag.to_string())),
+        )
+        .collect();
+    items.sort_by(|a, b| a.sort_text.cmp(&b.sort_text));
+    items
+}
+
+/// The byte index after the `^^` of the datatype that is being typed,
+/// `name_start` is the start of the (prefixed) name at the cursor.
+pub(super) fn datatype_start(
+    tree: &Tree,
+    text: &str,
+    name_start: usize,
+    cursor: usize,
+) -> Option<usize> {
+    text.get(..name_start)?.ends_with("^^").then_some(())?;
+    CompletionProbe::with_word_start(tree, text, name_start, cursor)?
+        .accepts("<d>", "IRIREF")
+        .then_some(name_start)
+}
+
+/// The `xsd:` datatypes, `rdf:langString` and `rdf:HTML` for the literal whose datatype
+/// starts at `start`.
+///
+/// Datatypes are written with a declared prefix, otherwise `xsd:` (or `rdf:`) is declared.
+/// If a prefixed name is typed (`typed_prefix`), only the datatypes of its namespace are offered.
+pub(super) fn datatype_completions(
+    tree: &Tree,
+    text: &str,
+    start: usize,
+    cursor: usize,
+    typed_prefix: Option<&str>,
+) -> Vec<CompletionItem> {
+    let prefix_map = get_prefix_map(tree.root_node(), text);
+    let prologue = find_prologue(tree.root_node());
+    let declarations = prefix_declarations(prologue, text);
+    let declared = |prefix: &str| prefix_map.get(&format!("{}:", prefix));
+    let typed_namespace = match typed_prefix {
+        Some(prefix) => match declared(prefix) {
+            Some(namespace) => Some(namespace.as_str()),
+            None => [XSD, RDF]
+                .into_iter()
+                .find(|namespace| usual_prefix(namespace) == prefix),
+        },
+        None => None,
+    };
+    if typed_prefix.is_some() && typed_namespace.is_none() {
+        return vec![];
+    }
+    let Some(edit_range) = range(text, start, cursor) else {
+        return vec![];
+    };
+    DATATYPES
+        .iter()
+        .filter(|(namespace, _, _)| typed_namespace.is_none_or(|typed| typed == *namespace))
+        .map(|&(namespace, name, detail)| {
+            let iri = format!("{}{}", namespace, name);
+            let prefix = typed_prefix.unwrap_or(usual_prefix(namespace));
+            let (written, declaration) = match compact_with_declared_prefix(&prefix_map, &iri) {
+                Some(curie) if typed_prefix.is_none() => (curie, None),
+                _ => match declared(prefix) {
+                    Some(declared) if declared == namespace => {
+                        (format!("{}:{}", prefix, name), None)
+                    }
+                    Some(_) => (format!("<{}>", iri), None),
+                    None => (
+                        format!("{}:{}", prefix, name),
+                        declaration_edit(prologue, &declarations, text, prefix, namespace),
+                    ),
+                },
+            };
+            let mut item = CompletionItem::new(
+                &written,
+                detail,
+                &written,
+                CompletionItemKind::Class,
+                InsertTextFormat::PlainText,
+            );
+            item.text_edit = Some(TextEdit::new(edit_range.clone(), &written));
+            item.additional_text_edits.extend(declaration);
+            item
+        })
+        .collect()
+}
@@ -1,11 +1,26 @@
+mod functions;
+mod keywords;
+mod literals;
+mod prefixes;
+mod probe;
+mod vocabulary;
+
+use functions::function_completions;
+use keywords::keyword_completions;
+use literals::{
+    datatype_completions, datatype_start, language_tag_completions, language_tag_start,
+};
 use log::{error, warn};
+use prefixes::{prefix_completions, prefix_declaration_completions};
+use probe::CompletionProbe;
+use vocabulary::{resolve_term, vocabulary_completions};
 
 use crate::server::{
-    anaysis::get_all_variables,
+    anaysis::ScopeModel,
     lsp::{
         errors::{ErrorCode, ResponseError},
-        CompletionItem, CompletionItemKind, CompletionRequest, CompletionResponse,
-        CompletionTriggerKind, InsertTextFormat,
+        CompletionItem, CompletionItemKind, CompletionRequest, CompletionResolveRequest,
+        CompletionResolveResponse, CompletionResponse, CompletionTriggerKind, InsertTextFormat,
     },
     Server,
 };
@@ -31,86 +46,47 @@ pub fn handle_completion_request(
     }
 }
 
+/// Adds the expensive parts of an item, e.g. the documentation, once it is selected.
+pub fn handle_completion_resolve_request(
+    server: &mut Server,
+    request: CompletionResolveRequest,
+) -> Result<CompletionResolveResponse, ResponseError> {
+    let id = request.get_id().clone();
+    Ok(CompletionResolveResponse::new(
+        &id,
+        resolve_term(server, request.params)?,
+    ))
+}
+
+/// The variables that are visible at the cursor, see `ScopeModel`.
 fn variable_completions(
     server: &Server,
     request: &CompletionRequest,
     triggered: bool,
-) -> Result<impl Iterator<Item = CompletionItem>, ResponseError> {
-    Ok(get_all_variables(
-        &server.state,
-        &request.get_text_position().text_document.uri,
-    )?
-    .into_iter()
-    .map(move |variable| {
-        CompletionItem::new(
-            &variable,
-            "variable",
-            match triggered {
-                true => &variable[1..],
-                false => &variable,
-            },
-            CompletionItemKind::Snippet,
-            InsertTextFormat::Snippet,
-        )
-    }))
-}
-
-fn graph_pattern_not_triples_completions(
-    _server: &Server,
-    _request: &CompletionRequest,
-) -> Result<impl Iterator<Item = CompletionItem>, ResponseError> {
-    Ok([
-        CompletionItem::new(
-            "FILTER",
-            "Filter the results",
-            "FILTER ( $0 )",
-            CompletionItemKind::Snippet,
-            InsertTextFormat::Snippet,
-        ),
-        CompletionItem::new(
-            "BIND",
-            "Bind a new variable",
-            "BIND ($1 AS ?$0)",
-            CompletionItemKind::Snippet,
-            InsertTextFormat::Snippet,
-        ),
-        CompletionItem::new(
-            "VALUES",
-            "Inline data definition",
-            "VALUES ?$1 { $0 }",
-            CompletionItemKind::Snippet,
-            InsertTextFormat::Snippet,
-        ),
-        CompletionItem::new(
-            "SERVICE",
-            "Collect data from a fedarated SPARQL endpoint",
-            "SERVICE <$1> {\n  $0\n}",
-            CompletionItemKind::Snippet,
-            InsertTextFormat::Snippet,
-        ),
-        CompletionItem::new(
-            "MINUS",
-            "Subtract data",
-            "MINUS { $0 }",
-            CompletionItemKind::Snippet,
-            InsertTextFormat::Snippet,
-        ),
-        CompletionItem::new(
-            "OPTIONAL",
-            "Optional graphpattern",
-            "OPTIONAL { $0 }",
-            CompletionItemKind::Snippet,
-            InsertTextFormat::Snippet,
-        ),
-        CompletionItem::new(
-            "UNION",
-            "Union of two results",
-            "{\n  $1\n}\nUNION\n{\n  $0\n}",
-            CompletionItemKind::Snippet,
-            InsertTextFormat::Snippet,
-        ),
-    ]
-    .into_iter())
+) -> Result<Vec<CompletionItem>, ResponseError> {
+    let text_position = request.get_text_position();
+    let (document, tree) = server.state.get_state(&text_position.text_document.uri)?;
+    let cursor = text_position
+        .position
+        .to_byte_index(&document.text)
+        .unwrap_or(document.text.len());
+    let scopes = ScopeModel::new(tree.root_node(), &document.text);
+    Ok(scopes
+        .visible_at(cursor)
+        .into_iter()
+        .map(|variable| {
+            CompletionItem::new(
+                variable,
+                "variable",
+                match triggered {
+                    true => &variable[1..],
+                    false => variable,
+                },
+                CompletionItemKind::Snippet,
+                InsertTextFormat::Snippet,
+            )
+        })
+        .collect())
 }
 
 fn collect_completions_triggered(
@@ -128,7 +104,8 @@ fn collect_completions_triggered(
                 "triggered completion request has no trigger character",
             ))?;
     Ok(match trigger_character.as_str() {
-        "?" => variable_completions(server, &request, true)?.collect(),
+        "?" => variable_completions(server, &request, true)?,
+        ":" | "@" | "^" => collect_completions(server, request)?,
         other => {
             warn!(
                 "Completion request triggered by unknown trigger character: \"{}\"",
@@ -143,49 +120,99 @@ fn collect_completions(
     server: &Server,
     request: &CompletionRequest,
 ) -> Result<Vec<CompletionItem>, ResponseError> {
-    let tree = server
-        .state
-        .get_tree(&request.get_text_position().text_document.uri)
-        .unwrap();
-    let position = &request.get_text_position().position;
-    let node = tree
-        .root_node()
-        .descendant_for_point_range(position.to_point(), position.to_point())
-        .unwrap();
-    Ok(match node.kind() {
-        "unit" => vec![
-            CompletionItem::new(
-                "SELECT",
-                "Select query",
-                "SELECT ${1:*} WHERE {\n  $0\n}",
-                CompletionItemKind::Snippet,
-                InsertTextFormat::Snippet,
-            ),
-            CompletionItem::new(
-                "PREFIX",
-                "Declare a namespace",
-                "PREFIX ${1:namespace}: <${0:iri}>",
-                CompletionItemKind::Snippet,
-                InsertTextFormat::Snippet,
-            ),
-            CompletionItem::new(
-                "ORDER BY",
-                "Sort the results",
-                "ORDER BY ${1|ASC,DESC|} ( $0 )",
-                CompletionItemKind::Snippet,
-                InsertTextFormat::Snippet,
-            ),
-            CompletionItem::new(
-                "BASE",
-                "Set the Base URI",
-                "BASE <${0}>",
-                CompletionItemKind::Snippet,
-                InsertTextFormat::Snippet,
+    let text_position = request.get_text_position();
+    let (document, tree) = server.state.get_state(&text_position.text_document.uri)?;
+    let cursor = text_position
+        .position
+        .to_byte_index(&document.text)
+        .ok_or(ResponseError::new(
+            ErrorCode::InvalidParams,
+            &format!(
+                "Position {} is not inside the document",
+                text_position.position
             ),
-        ],
-        "GroupGraphPattern" => variable_completions(server, request, false)?
-            .chain(graph_pattern_not_triples_completions(server, request)?)
-            .collect(),
-        _ => vec![],
-    })
+        ))?;
+    // NOTE: The language tag of a literal is typed.
+    if let Some(at) = language_tag_start(tree, &document.text, cursor) {
+        return Ok(language_tag_completions(tree, &document.text, at, cursor));
+    }
+    let mut probe = match CompletionProbe::new(tree, &document.text, cursor) {
+        Some(probe) => probe,
+        None => return Ok(vec![]),
+    };
+    // NOTE: The prefix of the prefixed name that is typed, and where the name starts.
+    let typed_prefix = match probe.char_before_word() {
+        Some(':') => probe.prefix_name(),
+        _ => None,
+    };
+    let name_start = typed_prefix.map_or(probe.word_start(), |prefix| {
+        probe.word_start() - prefix.len() - 1
+    });
+    // NOTE: The datatype of a literal is typed.
+    if let Some(start) = datatype_start(tree, &document.text, name_start, cursor) {
+        return Ok(datatype_completions(
+            tree,
+            &document.text,
+            start,
+            cursor,
+            typed_prefix,
+        ));
+    }
+    match probe.char_before_word() {
+        // NOTE: A variable is typed, only variables are valid.
+        Some('?') | Some('$') => {
+            return variable_completions(server, request, true);
+        }
+        // NOTE: The local part of a prefixed name is typed.
+        Some(':') => {
+            let mut completions = vec![];
+            if let Some(prefix) = typed_prefix {
+                if let Some(mut name_probe) =
+                    CompletionProbe::with_word_start(tree, &document.text, name_start, cursor)
+                {
+                    completions = vocabulary_completions(
+                        server,
+                        &text_position.text_document.uri,
+                        tree,
+                        &document.text,
+                        &mut name_probe,
+                        Some(prefix),
+                    );
+                }
+            }
+            completions.extend(prefix_declaration_completions(
+                server,
+                tree,
+                &document.text,
+                &mut probe,
+            ));
+            return Ok(completions);
+        }
+        _ => {}
+    }
+    let variables: Vec<CompletionItem> =
+        match probe.accepts("?v", "VAR") || probe.accepts("?v ?v ?v", "VAR") {
+            true => variable_completions(server, request, false)?,
+            false => vec![],
+        };
+    let terms = vocabulary_completions(
+        server,
+        &text_position.text_document.uri,
+        tree,
+        &document.text,
+        &mut probe,
+        None,
+    );
+    let prefixes = prefix_completions(server, tree, &document.text, &mut probe);
+    let keywords = keyword_completions(&mut probe);
+    Ok(terms
+        .into_iter()
+        .chain(variables)
+        .chain(prefixes)
+        .chain(keywords)
+        .chain(function_completions(&mut probe))
+        .collect())
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,274 @@
+use tree_sitter::{Node, Tree};
+
+use crate::server::{
+    lsp::{
+        textdocument::{Range, TextEdit},
+        CompletionItem, CompletionItemKind, InsertTextFormat,
+    },
+    Server,
+};
+
+use super::probe::CompletionProbe;
+
+/// A `PREFIX` declaration in the `Prologue` of a document.
+pub(super) struct PrefixDeclaration<'a> {
+    pub(super) prefix: &'a str,
+    namespace: &'a str,
+    node: Node<'a>,
+}
+
+/// The first `Prologue` of the document, updates can have several.
+pub(super) fn find_prologue(node: Node) -> Option<Node> {
+    if node.kind() == "Prologue" {
+        return Some(node);
+    }
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.children(&mut cursor).collect();
+    children.into_iter().find_map(find_prologue)
+}
+
+pub(super) fn prefix_declarations<'a>(
+    prologue: Option<Node<'a>>,
+    text: &'a str,
+) -> Vec<PrefixDeclaration<'a>> {
+    let prologue = match prologue {
+        Some(prologue) => prologue,
+        None => return vec![],
+    };
+    let mut cursor = prologue.walk();
+    let declarations: Vec<Node> = prologue
+        .children(&mut cursor)
+        .filter(|child| child.kind() == "PrefixDecl")
+        .collect();
+    declarations
+        .into_iter()
+        .filter_map(|node| {
+            let mut cursor = node.walk();
+            let children: Vec<Node> = node.children(&mut cursor).collect();
+            let namespace_node = children.iter().find(|child| child.kind() == "PNAME_NS")?;
+            let prefix = match namespace_node.child(0) {
+                Some(prefix) if prefix.kind() == "PN_PREFIX" => {
+                    prefix.utf8_text(text.as_bytes()).ok()?
+                }
+                _ => "",
+            };
+            let iri = children
+                .iter()
+                .find(|child| child.kind() == "IRIREF")?
+                .utf8_text(text.as_bytes())
+                .ok()?;
+            Some(PrefixDeclaration {
+                prefix,
+                namespace: iri.trim_start_matches('<').trim_end_matches('>'),
+                node,
+            })
+        })
+        .collect()
+}
+
+/// The edit that declares `prefix`.
+///
+/// The declaration is placed in alphabetical order between the existing declarations of the
+/// `Prologue`, after the `Prologue` if no existing declaration sorts after it, or at the start of
+/// the document if there is no `Prologue`.
+pub(super) fn declaration_edit(
+    prologue: Option<Node>,
+    declarations: &[PrefixDeclaration],
+    text: &str,
+    prefix: &str,
+    namespace: &str,
+) -> Option<TextEdit> {
+    let declaration = format!("PREFIX {}: <{}>", prefix, namespace);
+    if let Some(next) = declarations
+        .iter()
+        .find(|declaration| declaration.prefix > prefix)
+    {
+        let start = next.node.start_position();
+        let mut position = Range::from_ts_positions(start, start);
+        position.translate_to_utf16_encoding(text).ok()?;
+        let line_start = text[..next.node.start_byte()]
+            .rfind('\n')
+            .map_or(0, |index| index + 1);
+        let indentation = &text[line_start..next.node.start_byte()];
+        return Some(match indentation.chars().all(char::is_whitespace) {
+            true => TextEdit::new(position, &format!("{}\n{}", declaration, indentation)),
+            false => TextEdit::new(position, &format!("{} ", declaration)),
+        });
+    }
+    match prologue.filter(|prologue| prologue.child_count() > 0) {
+        Some(prologue) => {
+            let end = prologue.end_position();
+            let mut position = Range::from_ts_positions(end, end);
+            position.translate_to_utf16_encoding(text).ok()?;
+            Some(TextEdit::new(position, &format!("\n{}", declaration)))
+        }
+        None => Some(TextEdit::new(
+            Range::new(0, 0, 0, 0),
+            &format!("{}\n", declaration),
+        )),
+    }
+}
+
+fn prefix_completion(
+    label: &str,
+    namespace: &str,
+    insert_text: &str,
+    declaration: Option<TextEdit>,
+) -> CompletionItem {
+    let mut item = CompletionItem::new(
+        label,
+        namespace,
+        insert_text,
+        CompletionItemKind::Module,
+        InsertTextFormat::PlainText,
+    );
+    item.additional_text_edits.extend(declaration);
+    item
+}
+
+/// The prefixes that can be used at the cursor.
+///
+/// Declared prefixes come first, followed by the known prefixes of the `uri_converter`.
+/// Choosing an undeclared prefix also declares it.
+pub(super) fn prefix_completions(
+    server: &Server,
+    tree: &Tree,
+    text: &str,
+    probe: &mut CompletionProbe,
+) -> Vec<CompletionItem> {
+    if !probe.accepts("p:a", "PN_PREFIX") && !probe.accepts("p:a ?v ?v", "PN_PREFIX") {
+        return vec![];
+    }
+    let prologue = find_prologue(tree.root_node());
+    let declarations = prefix_declarations(prologue, text);
+    let mut known_prefixes: Vec<(String, String)> = server
+        .tools
+        .uri_converter
+        .write_prefix_map()
+        .into_iter()
+        .filter(|(prefix, _)| {
+            !declarations
+                .iter()
+                .any(|declaration| declaration.prefix == prefix)
+        })
+        .collect();
+    known_prefixes.sort();
+    declarations
+        .iter()
+        .map(|declaration| {
+            let label = format!("{}:", declaration.prefix);
+            prefix_completion(&label, declaration.namespace, &label, None)
+        })
+        .chain(known_prefixes.iter().map(|(prefix, namespace)| {
+            let label = format!("{}:", prefix);
+            let edit = declaration_edit(prologue, &declarations, text, prefix, namespace);
+            prefix_completion(&label, namespace, &label, edit)
+        }))
+        .collect()
+}
+
+/// Declares the prefix of the prefixed name that is being typed (e.g. `wd:|`),
+/// if it is known but not declared yet.
+pub(super) fn prefix_declaration_completions(
+    server: &Server,
+    tree: &Tree,
+    text: &str,
+    probe: &mut CompletionProbe,
+) -> Vec<CompletionItem> {
+    let prefix = match probe.prefix_name() {
+        Some(prefix) => prefix,
+        None => return vec![],
+    };
+    // NOTE: The prefix might be typed in its own declaration.
+    if !probe.accepts("a", "PN_LOCAL") {
+        return vec![];
+    }
+    let prologue = find_prologue(tree.root_node());
+    let declarations = prefix_declarations(prologue, text);
+    if declarations
+        .iter()
+        .any(|declaration| declaration.prefix == prefix)
+    {
+        return vec![];
+    }
+    match server.tools.uri_converter.find_by_prefix(prefix) {
+        Ok(record) => {
+            let edit = declaration_edit(prologue, &declarations, text, prefix, &record.uri_prefix);
+            vec![prefix_completion(
+                &format!("PREFIX {}: <{}>", prefix, record.uri_prefix),
+                "Declare prefix",
+                "",
+                edit,
+            )]
+        }
+        Err(_) => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tree_sitter::Parser;
+
+    use crate::server::lsp::textdocument::{Range, TextEdit};
+
+    use super::{declaration_edit, find_prologue, prefix_declarations};
+
+    fn edit(text: &str, prefix: &str) -> TextEdit {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_sparql::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(text, None).unwrap();
+        let prologue = find_prologue(tree.root_node());
+        let declarations = prefix_declarations(prologue, text);
+        declaration_edit(prologue, &declarations, text, prefix, "http://example.org/").unwrap()
+    }
+
+    #[test]
+    fn declaration_without_prologue() {
+        assert_eq!(
+            edit("SELECT * WHERE {}", "ex"),
+            TextEdit::new(Range::new(0, 0, 0, 0), "PREFIX ex: <http://example.org/>\n")
+        );
+    }
+
+    #[test]
+    fn declaration_in_alphabetical_order() {
+        let text = indoc!(
+            "PREFIX dc: <http://purl.org/dc/elements/1.1/>
+             PREFIX foaf: <http://xmlns.com/foaf/0.1/>
+             SELECT * WHERE {}"
+        );
+        assert_eq!(
+            edit(text, "ex"),
+            TextEdit::new(Range::new(1, 0, 1, 0), "PREFIX ex: <http://example.org/>\n")
+        );
+        assert_eq!(
+            edit(text, "a"),
+            TextEdit::new(Range::new(0, 0, 0, 0), "PREFIX a: <http://example.org/>\n")
+        );
+        assert_eq!(
+            edit(text, "rdf"),
+            TextEdit::new(
+                Range::new(1, 41, 1, 41),
+                "\nPREFIX rdf: <http://example.org/>"
+            )
+        );
+    }
+
+    #[test]
+    fn declaration_after_base() {
+        assert_eq!(
+            edit("BASE <http://example.org/>\nSELECT * WHERE {}", "ex"),
+            TextEdit::new(
+                Range::new(0, 26, 0, 26),
+                "\nPREFIX ex: <http://example.org/>"
+            )
+        );
+        assert_eq!(
+            edit("PREFIX b: <b> PREFIX d: <d>\nASK {}", "c"),
+            TextEdit::new(Range::new(0, 14, 0, 14), "PREFIX c: <http://example.org/> ")
+        );
+    }
+}
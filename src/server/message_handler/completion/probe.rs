@@ -0,0 +1,327 @@
+use tree_sitter::{InputEdit, Node, Parser, Point, Tree};
+
+use crate::server::lsp::textdocument::byte_index_to_point;
+
+/// Tests which tokens the grammar accepts at the cursor.
+///
+/// A candidate is spliced into the document at the cursor (replacing the word that is being typed)
+/// and the result is parsed. The candidate is accepted, if its first token is parsed as
+/// the expected token, is part of a grammar rule (and not directly of an `ERROR` node)
+/// and the candidate does not introduce new errors.
+/// Errors after the candidate are ignored, the rest of the document might be incomplete.
+///
+/// Brackets that are still open at the cursor (e.g. while typing `BIND(?x `) make
+/// the parser give up on the whole block, so a second attempt closes them after the candidate.
+///
+/// The documents are not parsed from scratch: the parse-tree of the document is edited and
+/// reparsed incrementally, so probing costs about the same for short and long documents.
+pub(super) struct CompletionProbe<'a> {
+    parser: Parser,
+    prefix: &'a str,
+    suffix: &'a str,
+    cursor: usize,
+    /// The position of the start of the word that is being typed.
+    start_position: Point,
+    /// The parse-tree of the document without the word that is being typed.
+    base_tree: Tree,
+    closers: String,
+    base_errors: usize,
+    base_errors_closed: usize,
+}
+
+/// A document with an accepted candidate spliced in at the cursor.
+pub(super) struct ProbedDocument {
+    pub(super) tree: Tree,
+    pub(super) text: String,
+    start: usize,
+}
+
+impl ProbedDocument {
+    /// The first token of the candidate.
+    pub(super) fn token(&self) -> Option<Node<'_>> {
+        self.tree
+            .root_node()
+            .descendant_for_byte_range(self.start, self.start + 1)
+    }
+}
+
+impl<'a> CompletionProbe<'a> {
+    /// Returns `None` if the cursor is not inside of the text.
+    /// `tree` is the parse-tree of `text`.
+    pub(super) fn new(tree: &Tree, text: &'a str, cursor: usize) -> Option<Self> {
+        Self::with_word_start(tree, text, word_start(text, cursor)?, cursor)
+    }
+
+    /// Probes `text` as if the word that is being typed started at `start`,
+    /// e.g. at the start of a prefixed name.
+    pub(super) fn with_word_start(
+        tree: &Tree,
+        text: &'a str,
+        start: usize,
+        cursor: usize,
+    ) -> Option<Self> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_sparql::LANGUAGE.into())
+            .ok()?;
+        let (prefix, suffix) = (text.get(..start)?, text.get(cursor..)?);
+        let start_position = byte_index_to_point(text, start);
+        let mut base_tree = tree.clone();
+        base_tree.edit(&InputEdit {
+            start_byte: start,
+            old_end_byte: cursor,
+            new_end_byte: start,
+            start_position,
+            old_end_position: byte_index_to_point(text, cursor),
+            new_end_position: start_position,
+        });
+        let base_tree = parser.parse(format!("{}{}", prefix, suffix), Some(&base_tree))?;
+        let closers = unclosed_brackets(&base_tree, prefix.len());
+        let base_errors = errors_until(base_tree.root_node(), start);
+        let base_errors_closed = match closers.is_empty() {
+            true => base_errors,
+            false => {
+                let mut closed_tree = base_tree.clone();
+                closed_tree.edit(&insertion(start, start_position, &closers));
+                parser
+                    .parse(
+                        format!("{}{}{}", prefix, closers, suffix),
+                        Some(&closed_tree),
+                    )
+                    .map_or(0, |tree| errors_until(tree.root_node(), start))
+            }
+        };
+        Some(Self {
+            parser,
+            prefix,
+            suffix,
+            cursor,
+            start_position,
+            base_tree,
+            closers,
+            base_errors,
+            base_errors_closed,
+        })
+    }
+
+    /// The byte index where the word that is being typed starts.
+    pub(super) fn word_start(&self) -> usize {
+        self.prefix.len()
+    }
+
+    /// The byte index of the cursor.
+    pub(super) fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// The character right before the word that is being typed.
+    pub(super) fn char_before_word(&self) -> Option<char> {
+        self.prefix.chars().last()
+    }
+
+    /// The prefix name of the prefixed name that is being typed,
+    /// `Some("wd")` for `wd:Q4|` or `wd:|`.
+    pub(super) fn prefix_name(&self) -> Option<&'a str> {
+        let namespace_end = self.prefix.strip_suffix(':')?.len();
+        let namespace_start = self.prefix[..namespace_end]
+            .char_indices()
+            .rev()
+            .take_while(|(_, char)| char.is_alphanumeric() || matches!(char, '_' | '-' | '.'))
+            .last()
+            .map_or(namespace_end, |(index, _)| index);
+        Some(&self.prefix[namespace_start..namespace_end])
+    }
+
+    /// Checks if `candidate` is valid at the cursor.
+    /// The first token of `candidate` has to be of kind `token_kind`.
+    pub(super) fn accepts(&mut self, candidate: &str, token_kind: &str) -> bool {
+        self.probe(candidate, token_kind).is_some()
+    }
+
+    /// Like `accepts`, but returns the document with the candidate, e.g. to inspect
+    /// the grammar rules around the candidate.
+    pub(super) fn probe(&mut self, candidate: &str, token_kind: &str) -> Option<ProbedDocument> {
+        self.probe_with(candidate, token_kind, "", self.base_errors)
+            .or_else(|| match self.closers.is_empty() {
+                true => None,
+                false => self.probe_with(
+                    candidate,
+                    token_kind,
+                    &self.closers.clone(),
+                    self.base_errors_closed,
+                ),
+            })
+    }
+
+    fn probe_with(
+        &mut self,
+        candidate: &str,
+        token_kind: &str,
+        closers: &str,
+        base_errors: usize,
+    ) -> Option<ProbedDocument> {
+        let start = self.prefix.len();
+        let inserted = format!("{} {}", candidate, closers);
+        let text = format!("{}{}{}", self.prefix, inserted, self.suffix);
+        let mut old_tree = self.base_tree.clone();
+        old_tree.edit(&insertion(start, self.start_position, &inserted));
+        let tree = self.parser.parse(&text, Some(&old_tree))?;
+        let token = tree
+            .root_node()
+            .descendant_for_byte_range(start, start + 1)?;
+        let accepted = token.kind() == token_kind
+            && token.start_byte() == start
+            && !token.is_missing()
+            && !token.parent().is_some_and(|parent| parent.is_error())
+            && errors_until(tree.root_node(), start + candidate.len()) <= base_errors;
+        accepted.then_some(ProbedDocument { tree, text, start })
+    }
+}
+
+/// The start of the (alphanumeric) word that ends at the cursor.
+fn word_start(text: &str, cursor: usize) -> Option<usize> {
+    Some(
+        text.get(..cursor)?
+            .char_indices()
+            .rev()
+            .take_while(|(_, char)| char.is_alphanumeric() || *char == '_')
+            .last()
+            .map_or(cursor, |(index, _)| index),
+    )
+}
+
+/// The edit that inserts `inserted` at the byte index `start` (at `start_position`).
+fn insertion(start: usize, start_position: Point, inserted: &str) -> InputEdit {
+    let new_end_position = match inserted.rfind('\n') {
+        Some(last_linebreak) => Point {
+            row: start_position.row + inserted.matches('\n').count(),
+            column: inserted.len() - last_linebreak - 1,
+        },
+        None => Point {
+            row: start_position.row,
+            column: start_position.column + inserted.len(),
+        },
+    };
+    InputEdit {
+        start_byte: start,
+        old_end_byte: start,
+        new_end_byte: start + inserted.len(),
+        start_position,
+        old_end_position: start_position,
+        new_end_position,
+    }
+}
+
+/// The closing brackets for all brackets that are opened before the cursor (at `cursor` in the
+/// document of `tree`) and never closed, innermost first.
+fn unclosed_brackets(tree: &Tree, cursor: usize) -> String {
+    let mut open: Vec<(usize, &str)> = vec![];
+    let mut stack = vec![tree.root_node()];
+    let mut leaves = vec![];
+    while let Some(node) = stack.pop() {
+        if node.child_count() == 0 {
+            leaves.push(node);
+        }
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+    for leaf in leaves.iter().filter(|leaf| !leaf.is_missing()) {
+        match leaf.kind() {
+            "(" => open.push((leaf.start_byte(), ")")),
+            "{" => open.push((leaf.start_byte(), "}")),
+            "[" => open.push((leaf.start_byte(), "]")),
+            closing @ (")" | "}" | "]")
+                if open
+                    .last()
+                    .is_some_and(|(_, expected)| *expected == closing) =>
+            {
+                open.pop();
+            }
+            _ => {}
+        }
+    }
+    open.iter()
+        .rev()
+        .filter(|(start, _)| *start < cursor)
+        .map(|(_, closing)| *closing)
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+/// Counts the `ERROR` and `MISSING` nodes that start before `end`.
+fn errors_until(node: Node, end: usize) -> usize {
+    if node.start_byte() > end || !node.has_error() {
+        return 0;
+    }
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.children(&mut cursor).collect();
+    (node.is_error() || node.is_missing()) as usize
+        + children
+            .into_iter()
+            .map(|child| errors_until(child, end))
+            .sum::<usize>()
+}
+
+#[cfg(test)]
+mod tests {
+    use tree_sitter::{Parser, Tree};
+
+    use super::{word_start, CompletionProbe};
+
+    /// The document without the `|`, its parse-tree and the cursor at the `|`.
+    fn probe(text: &str) -> (String, Tree, usize) {
+        let cursor = text.find('|').unwrap();
+        let text = text.replace('|', "");
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_sparql::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(&text, None).unwrap();
+        (text, tree, cursor)
+    }
+
+    #[test]
+    fn word_start_at_cursor() {
+        assert_eq!(word_start("SELECT * {} ORD", 15), Some(12));
+        assert_eq!(word_start("SELECT * {} ", 12), Some(12));
+        assert_eq!(word_start("?var", 4), Some(1));
+        assert_eq!(word_start("", 0), Some(0));
+        assert_eq!(word_start("a", 2), None);
+    }
+
+    #[test]
+    fn accepts_valid_tokens() {
+        let (text, tree, cursor) = probe("SELECT * WHERE { ?s ?p ?o } ORD|");
+        let mut probe = CompletionProbe::new(&tree, &text, cursor).unwrap();
+        assert!(probe.accepts("ORDER BY ?v", "ORDER"));
+        assert!(probe.accepts("LIMIT 1", "LIMIT"));
+        assert!(!probe.accepts("FILTER (?v)", "FILTER"));
+        assert!(!probe.accepts("WHERE {}", "WHERE"));
+    }
+
+    #[test]
+    fn accepts_in_unclosed_brackets() {
+        let (text, tree, cursor) = probe("SELECT * WHERE { BIND(?x |");
+        let mut probe = CompletionProbe::new(&tree, &text, cursor).unwrap();
+        assert!(probe.accepts("AS ?v", "AS"));
+        assert!(!probe.accepts("LIMIT 1", "LIMIT"));
+    }
+
+    #[test]
+    fn prefix_name_at_cursor() {
+        let prefix_name = |text: &str| {
+            let (text, tree, cursor) = probe(text);
+            CompletionProbe::new(&tree, &text, cursor)
+                .unwrap()
+                .prefix_name()
+                .map(|name| name.to_string())
+        };
+        assert_eq!(
+            prefix_name("SELECT * WHERE { ?s wdt:P3|"),
+            Some("wdt".to_string())
+        );
+        assert_eq!(prefix_name("SELECT * WHERE { ?s :|"), Some("".to_string()));
+        assert_eq!(prefix_name("SELECT * WHERE { ?s wdt|"), None);
+    }
+}
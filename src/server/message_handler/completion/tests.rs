@@ -0,0 +1,635 @@
+use curies::Converter;
+use indoc::indoc;
+use tree_sitter::Parser;
+use tree_sitter_sparql::LANGUAGE;
+
+use crate::server::{
+    lsp::{textdocument::TextDocumentItem, CompletionRequest, CompletionResolveRequest},
+    state::ServerState,
+    Server,
+};
+
+use super::{handle_completion_request, handle_completion_resolve_request};
+
+fn setup_server(text: &str) -> Server {
+    let mut server = Server::new(|_message| {});
+    let mut state = ServerState::new();
+    let mut parser = Parser::new();
+    parser.set_language(&LANGUAGE.into()).unwrap();
+    let document = TextDocumentItem::new("uri", text);
+    let tree = parser.parse(&document.text, None);
+    state.add_document(document, tree);
+    server.state = state;
+    let mut converter = Converter::new(":");
+    converter
+        .add_prefix("schema", "http://schema.org/")
+        .unwrap();
+    converter
+        .add_prefix("wd", "http://www.wikidata.org/entity/")
+        .unwrap();
+    server.tools.uri_converter = converter;
+    server
+}
+
+/// Collects the completions at the `|` in `text`.
+fn completions(text: &str, trigger_character: Option<&str>) -> Vec<serde_json::Value> {
+    completions_with_vocabulary(text, trigger_character, "")
+}
+
+/// Collects the completions at the `|` in `text`, with `vocabulary` (Turtle) loaded.
+fn completions_with_vocabulary(
+    text: &str,
+    trigger_character: Option<&str>,
+    vocabulary: &str,
+) -> Vec<serde_json::Value> {
+    let mut server = setup_server(&text.replace('|', ""));
+    server.tools.vocabulary.add_turtle(vocabulary).unwrap();
+    request_completions(&mut server, text, trigger_character)
+}
+
+/// Like `completions_with_vocabulary`, but each item is resolved (`completionItem/resolve`).
+fn resolved_completions_with_vocabulary(
+    text: &str,
+    trigger_character: Option<&str>,
+    vocabulary: &str,
+) -> Vec<serde_json::Value> {
+    let mut server = setup_server(&text.replace('|', ""));
+    server.tools.vocabulary.add_turtle(vocabulary).unwrap();
+    request_completions(&mut server, text, trigger_character)
+        .into_iter()
+        .map(|item| {
+            let request: CompletionResolveRequest = serde_json::from_value(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "completionItem/resolve",
+                "params": item
+            }))
+            .unwrap();
+            let response = handle_completion_resolve_request(&mut server, request).unwrap();
+            serde_json::to_value(response).unwrap()["result"].clone()
+        })
+        .collect()
+}
+
+/// Requests the completions at the `|` in `text`, the document of `server` is `text`
+/// without the `|`.
+fn request_completions(
+    server: &mut Server,
+    text: &str,
+    trigger_character: Option<&str>,
+) -> Vec<serde_json::Value> {
+    let cursor = text.find('|').unwrap();
+    let line = text[..cursor].matches('\n').count();
+    let line_start = text[..cursor].rfind('\n').map_or(0, |index| index + 1);
+    // NOTE: LSP counts the characters of a line in UTF-16 code units.
+    let character = text[line_start..cursor].encode_utf16().count();
+    let request: CompletionRequest = serde_json::from_value(serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "textDocument/completion",
+        "params": {
+            "textDocument": {"uri": "uri"},
+            "position": {"line": line, "character": character},
+            "context": match trigger_character {
+                Some(character) => serde_json::json!({"triggerKind": 2, "triggerCharacter": character}),
+                None => serde_json::json!({"triggerKind": 1}),
+            }
+        }
+    }))
+    .unwrap();
+    let response = handle_completion_request(server, request).unwrap();
+    serde_json::to_value(response).unwrap()["result"]["items"]
+        .as_array()
+        .unwrap()
+        .clone()
+}
+
+/// Collects the labels of the completions at the `|` in `text`.
+fn completion_labels(text: &str) -> Vec<String> {
+    completions(text, None)
+        .into_iter()
+        .map(|item| item["label"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn completion_empty_document() {
+    assert_eq!(
+        completion_labels("|"),
+        vec![
+            "PREFIX",
+            "BASE",
+            "SELECT",
+            "CONSTRUCT",
+            "DESCRIBE",
+            "ASK",
+            "INSERT DATA",
+            "DELETE DATA",
+            "DELETE WHERE",
+            "INSERT",
+            "DELETE"
+        ]
+    );
+}
+
+#[test]
+fn completion_after_select_clause() {
+    assert_eq!(
+        completion_labels("SELECT ?s |"),
+        vec!["?s", "FROM", "FROM NAMED", "WHERE"]
+    );
+}
+
+#[test]
+fn completion_after_where_clause() {
+    assert_eq!(
+        completion_labels(indoc!(
+            "SELECT * WHERE {
+               ?s ?p ?o
+             }
+             ORD|"
+        )),
+        vec!["GROUP BY", "HAVING", "ORDER BY", "LIMIT", "OFFSET", "VALUES"]
+    );
+    // NOTE: A group condition can also be a built-in call.
+    let labels = completion_labels("SELECT * WHERE { ?s ?p ?o } GROUP BY ?s |");
+    assert_eq!(
+        labels[..8],
+        ["?o", "?p", "?s", "HAVING", "ORDER BY", "LIMIT", "OFFSET", "VALUES"]
+    );
+    assert!(labels.contains(&"STRSTARTS".to_string()));
+    assert_eq!(
+        completion_labels("SELECT * WHERE { ?s ?p ?o } LIMIT 10 |"),
+        vec!["OFFSET", "VALUES"]
+    );
+}
+
+#[test]
+fn completion_in_group_graph_pattern() {
+    assert_eq!(
+        completion_labels(indoc!(
+            "SELECT * WHERE {
+               ?s ?p ?o .
+               |
+             }"
+        )),
+        vec![
+            "?o", "?p", "?s", "schema:", "wd:", "FILTER", "BIND", "VALUES", "SERVICE", "MINUS",
+            "OPTIONAL", "GRAPH"
+        ]
+    );
+}
+
+#[test]
+fn completion_in_bind() {
+    assert!(completion_labels("SELECT * WHERE { BIND (?x |) }").contains(&"AS".to_string()));
+    assert!(completion_labels("SELECT * WHERE { BIND (?x |").contains(&"AS".to_string()));
+    assert!(!completion_labels("SELECT * WHERE { BIND (?x |) }").contains(&"LIMIT".to_string()));
+}
+
+#[test]
+fn completion_in_aggregate() {
+    assert!(completion_labels("SELECT (COUNT(| ?x) AS ?count) WHERE {}")
+        .contains(&"DISTINCT".to_string()));
+    assert!(
+        completion_labels("SELECT (GROUP_CONCAT(?x; |) AS ?list) WHERE {}")
+            .contains(&"SEPARATOR".to_string())
+    );
+}
+
+#[test]
+fn completion_typed_variable() {
+    assert_eq!(
+        completion_labels("SELECT ?var WHERE { ?v| }"),
+        vec!["?v", "?var"]
+    );
+}
+
+#[test]
+fn completion_prefixes() {
+    let items = completions(
+        indoc!(
+            "PREFIX wd: <http://www.wikidata.org/entity/>
+             SELECT * WHERE {
+               ?s ?p s|
+             }"
+        ),
+        None,
+    );
+    let prefixes: Vec<&serde_json::Value> = items
+        .iter()
+        .filter(|item| item["label"].as_str().unwrap().ends_with(':'))
+        .collect();
+    assert_eq!(prefixes.len(), 2);
+    assert_eq!(prefixes[0]["label"], "wd:");
+    assert_eq!(prefixes[0].get("additionalTextEdits"), None);
+    assert_eq!(prefixes[1]["label"], "schema:");
+    assert_eq!(
+        prefixes[1]["additionalTextEdits"],
+        serde_json::json!([{
+            "range": {"start": {"line": 0, "character": 0}, "end": {"line": 0, "character": 0}},
+            "newText": "PREFIX schema: <http://schema.org/>\n"
+        }])
+    );
+}
+
+#[test]
+fn completion_prefix_declaration() {
+    let text = indoc!(
+        "PREFIX rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#>
+         PREFIX xsd: <http://www.w3.org/2001/XMLSchema#>
+         SELECT * WHERE {
+           ?s ?p wd:|
+         }"
+    );
+    let items = completions(text, Some(":"));
+    assert_eq!(items.len(), 1);
+    assert_eq!(
+        items[0]["label"],
+        "PREFIX wd: <http://www.wikidata.org/entity/>"
+    );
+    assert_eq!(items[0]["insertText"], "");
+    assert_eq!(
+        items[0]["additionalTextEdits"],
+        serde_json::json!([{
+            "range": {"start": {"line": 1, "character": 0}, "end": {"line": 1, "character": 0}},
+            "newText": "PREFIX wd: <http://www.wikidata.org/entity/>\n"
+        }])
+    );
+    // NOTE: declared and unknown prefixes need no declaration.
+    assert!(completions(&text.replace("wd:|", "rdf:|"), Some(":")).is_empty());
+    assert!(completions(&text.replace("wd:|", "ex:|"), Some(":")).is_empty());
+    assert!(completions("PREFIX wd:| <http://www.wikidata.org/entity/>", Some(":")).is_empty());
+}
+
+#[test]
+fn completion_functions() {
+    let items = completions("SELECT * WHERE { ?s ?p ?o FILTER (|) }", None);
+    let substr = items.iter().find(|item| item["label"] == "SUBSTR").unwrap();
+    assert_eq!(substr["kind"], 3);
+    assert_eq!(
+        substr["detail"],
+        "SUBSTR(source, start, [length]) -> string"
+    );
+    assert_eq!(substr["insertText"], "SUBSTR(${1:source}, ${2:start})");
+    assert_eq!(substr["documentation"]["kind"], "markdown");
+    assert!(substr["documentation"]["value"].is_string());
+    for text in [
+        "SELECT * WHERE { BIND (|",
+        "SELECT (| AS ?x) WHERE {}",
+        "SELECT * WHERE { ?s ?p ?o } GROUP BY ?s HAVING (|)",
+    ] {
+        let labels = completion_labels(text);
+        assert!(labels.contains(&"STRSTARTS".to_string()), "{}", text);
+        assert!(labels.contains(&"COUNT".to_string()), "{}", text);
+    }
+    assert!(!completion_labels("SELECT * WHERE { ?s ?p ?o . | }").contains(&"REGEX".to_string()));
+}
+
+const SCHEMA_VOCABULARY: &str = indoc!(
+    r#"
+    @prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .
+    @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+    @prefix schema: <http://schema.org/> .
+
+    schema:Person a rdfs:Class ;
+        rdfs:label "Person" ;
+        rdfs:comment "A person (alive, dead, undead, or fictional)." .
+    schema:Employee rdfs:subClassOf schema:Person ; rdfs:label "Employee" .
+    schema:Place a rdfs:Class ; rdfs:label "Place" .
+    schema:birthDate a rdf:Property ; rdfs:label "birth date" ; rdfs:domain schema:Person .
+    schema:address a rdf:Property ; rdfs:label "address" ; rdfs:domain schema:Place .
+    schema:name a rdf:Property .
+    "#
+);
+
+fn labels(items: &[serde_json::Value]) -> Vec<&str> {
+    items
+        .iter()
+        .map(|item| item["label"].as_str().unwrap())
+        .collect()
+}
+
+#[test]
+fn completion_vocabulary_properties() {
+    let items = completions_with_vocabulary(
+        "PREFIX schema: <http://schema.org/>\nSELECT * WHERE { ?s a schema:Employee ; | }",
+        None,
+        SCHEMA_VOCABULARY,
+    );
+    // NOTE: An employee is a person, properties of persons come first.
+    assert_eq!(
+        labels(&items)[..3],
+        ["birth date", "address", "schema:name"]
+    );
+    assert_eq!(items[0]["kind"], 10);
+    assert_eq!(items[0]["insertText"], "schema:birthDate");
+    assert_eq!(items[0]["filterText"], "birthDate birth date");
+    assert_eq!(items[2]["filterText"], "name");
+    // NOTE: The detail is resolved lazily.
+    assert!(items[0]["detail"].is_null());
+    assert_eq!(
+        items[0]["data"],
+        serde_json::json!({"uri": "uri", "iri": "http://schema.org/birthDate"})
+    );
+
+    // NOTE: Undeclared prefixes get declared.
+    let items = completions_with_vocabulary("SELECT * WHERE { ?s | }", None, SCHEMA_VOCABULARY);
+    assert_eq!(
+        labels(&items)[..3],
+        ["address", "birth date", "schema:name"]
+    );
+    assert_eq!(items[0]["insertText"], "schema:address");
+    assert!(items[0]["additionalTextEdits"].is_null());
+    let items = completions_with_vocabulary("SELECT * WHERE { ?s ?p | }", None, SCHEMA_VOCABULARY);
+    assert!(!items.iter().any(|item| item["kind"] == 10));
+}
+
+#[test]
+fn completion_vocabulary_classes() {
+    let items = completions_with_vocabulary("SELECT * WHERE { ?s a | }", None, SCHEMA_VOCABULARY);
+    assert_eq!(labels(&items)[..3], ["Employee", "Person", "Place"]);
+    assert_eq!(items[1]["kind"], 7);
+    assert!(items[1]["documentation"].is_null());
+    let items = completions_with_vocabulary(
+        "PREFIX rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#>\nSELECT * WHERE { ?s rdf:type | }",
+        None,
+        SCHEMA_VOCABULARY,
+    );
+    assert_eq!(labels(&items)[..3], ["Employee", "Person", "Place"]);
+    let items = completions_with_vocabulary("SELECT * WHERE { ?s ?p | }", None, SCHEMA_VOCABULARY);
+    assert!(!items.iter().any(|item| item["kind"] == 7));
+}
+
+#[test]
+fn completion_vocabulary_typed_prefix() {
+    let items = completions_with_vocabulary(
+        "PREFIX schema: <http://schema.org/>\nSELECT * WHERE { ?s a schema:Pe| }",
+        None,
+        SCHEMA_VOCABULARY,
+    );
+    assert_eq!(labels(&items), ["Employee", "Person", "Place"]);
+    assert_eq!(items[1]["insertText"], "Person");
+    assert_eq!(
+        items[1]["textEdit"],
+        serde_json::json!({
+            "range": {
+                "start": {"line": 1, "character": 22},
+                "end": {"line": 1, "character": 31}
+            },
+            "newText": "schema:Person"
+        })
+    );
+    assert_eq!(items[1]["filterText"], "schema:Person");
+
+    // NOTE: The prefix is not declared yet, the declaration is offered as well.
+    let items = completions_with_vocabulary(
+        "SELECT * WHERE { ?s schema:| }",
+        Some(":"),
+        SCHEMA_VOCABULARY,
+    );
+    assert_eq!(
+        labels(&items),
+        [
+            "address",
+            "birth date",
+            "schema:name",
+            "PREFIX schema: <http://schema.org/>"
+        ]
+    );
+    assert_eq!(items[0]["insertText"], "address");
+    assert_eq!(items[0]["filterText"], "schema:address");
+    assert_eq!(items[0]["textEdit"]["newText"], "schema:address");
+}
+
+#[test]
+fn completion_resolve() {
+    let items =
+        resolved_completions_with_vocabulary("SELECT * WHERE { ?s a | }", None, SCHEMA_VOCABULARY);
+    assert_eq!(items[1]["label"], "Person");
+    assert_eq!(items[1]["detail"], "schema:Person");
+    assert_eq!(
+        items[1]["documentation"],
+        serde_json::json!({
+            "kind": "markdown",
+            "value": "A person (alive, dead, undead, or fictional)."
+        })
+    );
+    assert_eq!(
+        items[1]["additionalTextEdits"][0]["newText"],
+        "PREFIX schema: <http://schema.org/>\n"
+    );
+    assert!(items[0]["documentation"].is_null());
+
+    // NOTE: Declared prefixes are not declared again.
+    let items = resolved_completions_with_vocabulary(
+        "PREFIX schema: <http://schema.org/>\nSELECT * WHERE { ?s schema:| }",
+        Some(":"),
+        SCHEMA_VOCABULARY,
+    );
+    assert_eq!(items[1]["detail"], "schema:birthDate");
+    assert!(items[1]["additionalTextEdits"].is_null());
+
+    // NOTE: Items of other completions are unchanged.
+    let items = resolved_completions_with_vocabulary("SELECT * WHERE { ?s ?p | }", None, "");
+    assert_eq!(items, completions("SELECT * WHERE { ?s ?p | }", None));
+}
+
+#[test]
+fn completion_language_tags() {
+    let text = indoc!(
+        r#"SELECT * WHERE {
+             ?s rdfs:label "Berlin"@de, "Berlin"@eng, "Berlino"@it, "Berlin"@de-CH, "Berlin"@de .
+             ?s rdfs:label "Berlin"@|
+           }"#
+    );
+    let items = completions(text, Some("@"));
+    // NOTE: Used tags come first, the most used first, misspelled tags are not suggested.
+    assert_eq!(labels(&items)[..4], ["de", "de-CH", "it", "ar"]);
+    assert!(!labels(&items).contains(&"eng"));
+    let english = items.iter().find(|item| item["label"] == "en").unwrap();
+    assert_eq!(english["detail"], "English");
+    assert_eq!(english["filterText"], "en eng English");
+    assert_eq!(
+        english["textEdit"],
+        serde_json::json!({
+            "range": {
+                "start": {"line": 2, "character": 25},
+                "end": {"line": 2, "character": 25}
+            },
+            "newText": "en"
+        })
+    );
+
+    // NOTE: The typed tag is replaced, including its subtags.
+    let items = completions(&text.replace("@|", "@en-U|"), None);
+    assert_eq!(items[0]["textEdit"]["range"]["end"]["character"], 29);
+    assert!(!labels(&items).contains(&"en-U"));
+
+    assert!(!completions("SELECT * WHERE { ?s ?p ?o }\n# mail@|", None)
+        .iter()
+        .any(|item| item["label"] == "en"));
+}
+
+#[test]
+fn completion_datatypes() {
+    let items = completions("SELECT * WHERE { ?s ?p \"1\"^^| }", Some("^"));
+    assert_eq!(items.len(), 29);
+    let integer = items
+        .iter()
+        .find(|item| item["label"] == "xsd:integer")
+        .unwrap();
+    assert_eq!(integer["detail"], "Arbitrary size integer");
+    assert_eq!(
+        integer["additionalTextEdits"][0]["newText"],
+        "PREFIX xsd: <http://www.w3.org/2001/XMLSchema#>\n"
+    );
+    assert!(labels(&items).contains(&"rdf:langString"));
+    assert!(labels(&items).contains(&"rdf:HTML"));
+
+    // NOTE: Declared prefixes are used, whatever their name.
+    let text = indoc!(
+        "PREFIX x: <http://www.w3.org/2001/XMLSchema#>
+         SELECT * WHERE { ?s ?p \"1\"^^| }"
+    );
+    let items = completions(text, None);
+    let integer = items
+        .iter()
+        .find(|item| item["label"] == "x:integer")
+        .unwrap();
+    assert!(integer["additionalTextEdits"].is_null());
+
+    // NOTE: Only the datatypes of a typed prefix are offered, the typed name is replaced.
+    let items = completions(&text.replace("^^|", "^^x:in|"), None);
+    assert_eq!(items.len(), 27);
+    assert_eq!(
+        items[3]["textEdit"],
+        serde_json::json!({
+            "range": {
+                "start": {"line": 1, "character": 28},
+                "end": {"line": 1, "character": 32}
+            },
+            "newText": "x:integer"
+        })
+    );
+    let items = completions("SELECT * WHERE { ?s ?p \"1\"^^rdf:| }", Some(":"));
+    assert_eq!(labels(&items)[..2], ["rdf:langString", "rdf:HTML"]);
+    assert_eq!(
+        items[0]["additionalTextEdits"][0]["newText"],
+        "PREFIX rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#>\n"
+    );
+}
+
+#[test]
+fn completion_literals_non_ascii() {
+    // NOTE: The ranges are in UTF-16 columns, "ü" is one column but two bytes.
+    let items = completions("SELECT * WHERE { ?s ?p \"München\"@d| }", None);
+    assert_eq!(
+        items[0]["textEdit"]["range"],
+        serde_json::json!({
+            "start": {"line": 0, "character": 33},
+            "end": {"line": 0, "character": 34}
+        })
+    );
+    let text = indoc!(
+        "PREFIX ex: <http://example.org/Zürich/>
+         SELECT * WHERE { ?s ?p \"Zürich\"^^xsd:in| }"
+    );
+    let items = completions(text, None);
+    let integer = items
+        .iter()
+        .find(|item| item["label"] == "xsd:integer")
+        .unwrap();
+    assert_eq!(
+        integer["textEdit"]["range"],
+        serde_json::json!({
+            "start": {"line": 1, "character": 33},
+            "end": {"line": 1, "character": 39}
+        })
+    );
+    assert_eq!(
+        integer["additionalTextEdits"][0]["range"],
+        serde_json::json!({
+            "start": {"line": 0, "character": 39},
+            "end": {"line": 0, "character": 39}
+        })
+    );
+    let items = completions_with_vocabulary(
+        "PREFIX schema: <http://schema.org/>\nSELECT * WHERE { ?s schema:name \"Zürich\" ; schema:add| }",
+        None,
+        SCHEMA_VOCABULARY,
+    );
+    let address = items
+        .iter()
+        .find(|item| item["label"] == "address")
+        .unwrap();
+    assert_eq!(
+        address["textEdit"]["range"],
+        serde_json::json!({
+            "start": {"line": 1, "character": 43},
+            "end": {"line": 1, "character": 53}
+        })
+    );
+}
+
+#[test]
+fn completion_variables_in_scope() {
+    let text = indoc!(
+        "SELECT ?item WHERE {
+           ?item ?p ?o .
+           { SELECT ?count WHERE { ?other ?q ?z } }
+           FILTER (|)
+         }"
+    );
+    let labels = completion_labels(text);
+    assert!(labels.contains(&"?count".to_string()));
+    assert!(labels.contains(&"?item".to_string()));
+    assert!(!labels.contains(&"?other".to_string()));
+}
+
+/// A query with `blocks` groups of triples, `OPTIONAL`, `BIND`, `UNION`, `FILTER` and `MINUS`,
+/// six lines each, and a subquery every tenth group. A variable is typed at the `|` at the end.
+fn large_query(blocks: usize) -> String {
+    let mut text = "PREFIX ex: <http://example.org/>\nSELECT * WHERE {\n".to_string();
+    for i in 0..blocks {
+        text += &format!(
+            "  ?s{i} ex:p ?o{i} .\n  OPTIONAL {{ ?o{i} ex:q ?v{i} }}\n  BIND (?v{i} AS ?b{i})\n  {{ ?s{i} ex:r ?x{i} }} UNION {{ ?s{i} ex:t ?y{i} }}\n  FILTER (?o{i} != ?b{i})\n  MINUS {{ ?s{i} ex:u ?m{i} }}\n"
+        );
+        if i % 10 == 0 {
+            text += &format!("  {{ SELECT ?s{i} WHERE {{ ?s{i} ex:w ?w{i} }} }}\n");
+        }
+    }
+    text + "  FILTER (?|)\n}\n"
+}
+
+#[test]
+fn completion_variables_large_document() {
+    let text = large_query(500);
+    let mut server = setup_server(&text.replace('|', ""));
+    let start = std::time::Instant::now();
+    let items = request_completions(&mut server, &text, Some("?"));
+    // NOTE: Generous for debug builds, quadratic scope analysis takes minutes.
+    assert!(start.elapsed() < std::time::Duration::from_secs(2));
+    assert!(labels(&items).contains(&"?b499"));
+    assert!(!labels(&items).contains(&"?m499"));
+    assert!(!labels(&items).contains(&"?w490"));
+}
+
+#[test]
+fn completion_keywords_large_document() {
+    for (text, keyword) in [
+        (
+            large_query(500).replace("  FILTER (?|)\n}\n", "}\nORD|"),
+            "ORDER BY",
+        ),
+        (large_query(500).replace("FILTER (?|)", "FIL|"), "FILTER"),
+    ] {
+        let mut server = setup_server(&text.replace('|', ""));
+        let start = std::time::Instant::now();
+        let items = request_completions(&mut server, &text, None);
+        // NOTE: Generous for debug builds, parsing the document for every candidate takes
+        // seconds.
+        assert!(start.elapsed() < std::time::Duration::from_secs(2));
+        assert!(labels(&items).contains(&keyword));
+    }
+}
@@ -0,0 +1,349 @@
+use std::collections::{HashMap, HashSet};
+
+use tree_sitter::{Node, Tree};
+
+use crate::server::{
+    anaysis::{compact_with_declared_prefix, get_prefix_map, is_simple_local_name, resolve_iri},
+    lsp::{
+        base_types::LSPAny,
+        errors::ResponseError,
+        textdocument::{byte_index_to_point, Range, TextEdit},
+        CompletionItem, CompletionItemKind, InsertTextFormat, MarkupContent,
+    },
+    vocabulary::{namespace_of, Term},
+    Server,
+};
+
+use super::{
+    prefixes::{declaration_edit, find_prologue, prefix_declarations},
+    probe::CompletionProbe,
+};
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+
+/// The rules between the verb of a triple and the IRI it consists of.
+const PATH_KINDS: [&str; 6] = [
+    "PathPrimary",
+    "PathElt",
+    "PathEltOrInverse",
+    "PathSequence",
+    "PathAlternative",
+    "Path",
+];
+
+/// The kind of term the grammar expects at the cursor.
+enum Slot {
+    /// The verb of a triple, with the classes of its subject.
+    Property(HashSet<String>),
+    /// The object of `a` or `rdf:type`.
+    Class,
+}
+
+/// The `IRIREF` or `PrefixedName` a node consists of, e.g. the IRI of a `Path` without operators.
+fn sole_iri(node: Node) -> Option<Node> {
+    let mut iri = node;
+    while !matches!(iri.kind(), "IRIREF" | "PrefixedName") {
+        iri = iri.child(0)?;
+    }
+    (iri.byte_range() == node.byte_range()).then_some(iri)
+}
+
+/// Checks if the verb of a triple denotes `rdf:type`.
+fn is_type_verb(verb: Node, prefix_map: &HashMap<String, String>, text: &str) -> bool {
+    verb.utf8_text(text.as_bytes()) == Ok("a")
+        || sole_iri(verb)
+            .and_then(|iri| resolve_iri(&iri, prefix_map, text))
+            .is_some_and(|iri| iri == RDF_TYPE)
+}
+
+/// The verb of the triple, if the token is (the start of) a verb.
+fn verb_of(token: Node) -> Option<Node> {
+    let mut verb = token;
+    while let Some(parent) = verb
+        .parent()
+        .filter(|parent| PATH_KINDS.contains(&parent.kind()))
+    {
+        verb = parent;
+    }
+    verb.parent()
+        .filter(|parent| {
+            matches!(
+                parent.kind(),
+                "PropertyListPathNotEmpty" | "PropertyListNotEmpty"
+            )
+        })
+        .map(|_| verb)
+}
+
+fn collect_triples<'a>(node: Node<'a>, triples: &mut Vec<Node<'a>>) {
+    if matches!(node.kind(), "TriplesSameSubjectPath" | "TriplesSameSubject") {
+        triples.push(node);
+    }
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.children(&mut cursor).collect();
+    for child in children {
+        collect_triples(child, triples);
+    }
+}
+
+/// The classes of the subject of the triple `verb` belongs to, as stated by the triples of the
+/// document, and their super classes.
+fn subject_classes(server: &Server, verb: Node, text: &str) -> HashSet<String> {
+    let Some(subject) = verb
+        .parent()
+        .and_then(|property_list| property_list.parent())
+        .filter(|triple| {
+            matches!(
+                triple.kind(),
+                "TriplesSameSubjectPath" | "TriplesSameSubject"
+            )
+        })
+        .and_then(|triple| triple.child(0))
+        .and_then(|subject| subject.utf8_text(text.as_bytes()).ok())
+    else {
+        return HashSet::new();
+    };
+    let mut root = verb;
+    while let Some(parent) = root.parent() {
+        root = parent;
+    }
+    let prefix_map = get_prefix_map(root, text);
+    let mut triples = vec![];
+    collect_triples(root, &mut triples);
+    let mut classes = HashSet::new();
+    for property_list in triples
+        .into_iter()
+        .filter(|triple| {
+            triple
+                .child(0)
+                .is_some_and(|node| node.utf8_text(text.as_bytes()).ok() == Some(subject))
+        })
+        .filter_map(|triple| triple.child(1))
+    {
+        let mut cursor = property_list.walk();
+        let children: Vec<Node> = property_list.children(&mut cursor).collect();
+        for (verb, objects) in children.iter().zip(children.iter().skip(1)) {
+            if !matches!(objects.kind(), "ObjectList" | "ObjectListPath")
+                || !is_type_verb(*verb, &prefix_map, text)
+            {
+                continue;
+            }
+            let mut cursor = objects.walk();
+            let objects: Vec<Node> = objects.named_children(&mut cursor).collect();
+            classes.extend(
+                objects
+                    .into_iter()
+                    .filter_map(sole_iri)
+                    .filter_map(|iri| resolve_iri(&iri, &prefix_map, text)),
+            );
+        }
+    }
+    classes
+        .iter()
+        .flat_map(|class| server.tools.vocabulary.super_classes_of(class))
+        .map(|class| class.to_string())
+        .collect()
+}
+
+fn completion_slot(server: &Server, probe: &mut CompletionProbe) -> Option<Slot> {
+    if let Some(document) = probe.probe("<p> ?v", "IRIREF") {
+        if let Some(verb) = document.token().and_then(verb_of) {
+            return Some(Slot::Property(subject_classes(
+                server,
+                verb,
+                &document.text,
+            )));
+        }
+    }
+    let document = probe.probe("<c>", "IRIREF")?;
+    let verb = document
+        .token()?
+        .parent()
+        .filter(|parent| matches!(parent.kind(), "ObjectList" | "ObjectListPath"))?
+        .prev_sibling()?;
+    let prefix_map = get_prefix_map(document.tree.root_node(), &document.text);
+    is_type_verb(verb, &prefix_map, &document.text).then_some(Slot::Class)
+}
+
+/// How a term is written at the cursor.
+struct Insertion {
+    /// The term as written in the document, e.g. `ex:knows`.
+    display: String,
+    insert_text: String,
+    /// Replaces the typed prefixed name, e.g. `ex:kn`.
+    text_edit: Option<TextEdit>,
+}
+
+/// The document and term of a vocabulary completion, resolved by `resolve_term`.
+fn term_data(uri: &str, iri: &str) -> LSPAny {
+    LSPAny::LSPObject(HashMap::from([
+        ("uri".to_string(), LSPAny::String(uri.to_string())),
+        ("iri".to_string(), LSPAny::String(iri.to_string())),
+    ]))
+}
+
+/// The item is kept small, the detail, documentation and prefix declaration are added by
+/// `resolve_term`.
+fn completion_item(uri: &str, term: &Term, kind: &Slot, insertion: Insertion) -> CompletionItem {
+    let local_name = &term.iri[namespace_of(&term.iri).len()..];
+    let label = term.label().unwrap_or(&insertion.display);
+    let (item_kind, rank) = match kind {
+        Slot::Property(classes) => (
+            CompletionItemKind::Property,
+            match term.domains.iter().any(|domain| classes.contains(domain)) {
+                true => 0,
+                false => 1,
+            },
+        ),
+        Slot::Class => (CompletionItemKind::Class, 1),
+    };
+    let mut item = CompletionItem::new(
+        label,
+        &insertion.display,
+        &insertion.insert_text,
+        item_kind,
+        InsertTextFormat::PlainText,
+    );
+    item.detail = None;
+    item.sort_text = Some(format!("{}{}", rank, label.to_lowercase()));
+    // NOTE: Clients match the filter text against the text the edit replaces.
+    let filter_prefix = match &insertion.text_edit {
+        Some(_) => &insertion.display[..insertion.display.len() - local_name.len()],
+        None => "",
+    };
+    item.filter_text = Some(match term.label() {
+        Some(label) if label != local_name => {
+            format!("{}{} {}", filter_prefix, local_name, label)
+        }
+        _ => format!("{}{}", filter_prefix, local_name),
+    });
+    item.text_edit = insertion.text_edit;
+    item.data = Some(term_data(uri, &term.iri));
+    item
+}
+
+/// Adds the detail, documentation and, if the prefix of the inserted prefixed name is not
+/// declared (anymore), its declaration to a vocabulary completion.
+///
+/// Items of other completions are returned unchanged.
+pub(super) fn resolve_term(
+    server: &Server,
+    mut item: CompletionItem,
+) -> Result<CompletionItem, ResponseError> {
+    let (uri, iri) = match &item.data {
+        Some(LSPAny::LSPObject(data)) => match (data.get("uri"), data.get("iri")) {
+            (Some(LSPAny::String(uri)), Some(LSPAny::String(iri))) => (uri.clone(), iri.clone()),
+            _ => return Ok(item),
+        },
+        _ => return Ok(item),
+    };
+    let (document, tree) = server.state.get_state(&uri)?;
+    let text = &document.text;
+    item.detail = Some(item.written_text().to_string());
+    item.documentation = server
+        .tools
+        .vocabulary
+        .get(&iri)
+        .and_then(Term::comment)
+        .map(MarkupContent::markdown);
+    let Some((prefix, _)) = item
+        .written_text()
+        .split_once(':')
+        .filter(|_| !item.written_text().starts_with('<'))
+    else {
+        return Ok(item);
+    };
+    let prefix = prefix.to_string();
+    if get_prefix_map(tree.root_node(), text).contains_key(&format!("{}:", prefix)) {
+        return Ok(item);
+    }
+    if let Ok(record) = server.tools.uri_converter.find_by_prefix(&prefix) {
+        let prologue = find_prologue(tree.root_node());
+        let declarations = prefix_declarations(prologue, text);
+        item.additional_text_edits =
+            declaration_edit(prologue, &declarations, text, &prefix, &record.uri_prefix)
+                .into_iter()
+                .collect();
+    }
+    Ok(item)
+}
+
+/// Properties in the verb position of a triple and classes after `a` or `rdf:type`,
+/// from the loaded vocabularies.
+///
+/// Terms are written with a declared prefix, or with a known prefix that gets declared.
+/// If a prefixed name is typed (`typed_prefix` is `ex` for `ex:kn|`), only the terms of its
+/// namespace are offered and the typed prefixed name is replaced.
+/// Properties whose domain is a class of the subject come first.
+pub(super) fn vocabulary_completions(
+    server: &Server,
+    uri: &str,
+    tree: &Tree,
+    text: &str,
+    probe: &mut CompletionProbe,
+    typed_prefix: Option<&str>,
+) -> Vec<CompletionItem> {
+    let vocabulary = &server.tools.vocabulary;
+    if vocabulary.is_empty() {
+        return vec![];
+    }
+    let Some(slot) = completion_slot(server, probe) else {
+        return vec![];
+    };
+    let prefix_map = get_prefix_map(tree.root_node(), text);
+    // NOTE: The namespace of the typed prefix, declared or known.
+    let typed_namespace = match typed_prefix {
+        Some(prefix) => match prefix_map.get(&format!("{}:", prefix)) {
+            Some(namespace) => Some((prefix, namespace.clone())),
+            None => match server.tools.uri_converter.find_by_prefix(prefix) {
+                Ok(record) => Some((prefix, record.uri_prefix.clone())),
+                Err(_) => return vec![],
+            },
+        },
+        None => None,
+    };
+    // NOTE: The typed prefixed name starts at the start of the probed word.
+    let mut typed_range = Range::from_ts_positions(
+        byte_index_to_point(text, probe.word_start()),
+        byte_index_to_point(text, probe.cursor()),
+    );
+    if typed_range.translate_to_utf16_encoding(text).is_err() {
+        return vec![];
+    }
+    let terms: Vec<&Term> = match slot {
+        Slot::Property(_) => vocabulary.properties().collect(),
+        Slot::Class => vocabulary.classes().collect(),
+    };
+    let mut items: Vec<CompletionItem> = terms
+        .into_iter()
+        .filter_map(|term| {
+            let insertion = match &typed_namespace {
+                Some((prefix, namespace)) => {
+                    let local_name = term
+                        .iri
+                        .strip_prefix(namespace.as_str())
+                        .filter(|local_name| is_simple_local_name(local_name))?;
+                    let curie = format!("{}:{}", prefix, local_name);
+                    Insertion {
+                        insert_text: local_name.to_string(),
+                        text_edit: Some(TextEdit::new(typed_range.clone(), &curie)),
+                        display: curie,
+                    }
+                }
+                None => {
+                    let display = compact_with_declared_prefix(&prefix_map, &term.iri)
+                        .or_else(|| server.shorten_uri(&term.iri).map(|(_, _, curie)| curie))
+                        .unwrap_or_else(|| format!("<{}>", term.iri));
+                    Insertion {
+                        insert_text: display.clone(),
+                        text_edit: None,
+                        display,
+                    }
+                }
+            };
+            Some(completion_item(uri, term, &slot, insertion))
+        })
+        .collect();
+    items.sort_by(|a, b| a.sort_text.cmp(&b.sort_text));
+    items
+}
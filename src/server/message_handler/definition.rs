@@ -0,0 +1,17 @@
+use crate::server::{
+    anaysis::get_definition,
+    lsp::{errors::ResponseError, textdocument::Location, DefinitionRequest, DefinitionResponse},
+    Server,
+};
+
+pub fn handle_definition_request(
+    server: &mut Server,
+    request: DefinitionRequest,
+) -> Result<DefinitionResponse, ResponseError> {
+    let document_uri = request.get_document_uri();
+    let range = get_definition(&server.state, document_uri, request.get_position())?;
+    Ok(DefinitionResponse::new(
+        request.get_id(),
+        range.map(|range| Location::new(document_uri, range)),
+    ))
+}
@@ -1,12 +1,20 @@
+use log::error;
+
 use crate::server::{
-    anaysis::{get_all_uncompacted_uris, get_undeclared_prefixes, get_unused_prefixes},
+    anaysis::{
+        get_all_uncompacted_uris, get_resolved_iris, get_syntax_errors, get_unbound_projections,
+        get_undeclared_prefixes, get_unused_prefixes,
+    },
     lsp::{
         base_types::LSPAny,
         diagnostic::{Diagnostic, DiagnosticCode, DiagnosticSeverity},
         errors::ResponseError,
-        textdocument::TextDocumentItem,
-        DiagnosticRequest, DiagnosticResponse,
+        rpc::NotificationMessageBase,
+        textdocument::{Range, TextDocumentItem},
+        DiagnosticRequest, DiagnosticResponse, PublishDiagnosticsNotification,
+        PublishDiagnosticsPrarams,
     },
+    state::{now, ServerStatus},
     Server,
 };
 
@@ -26,9 +34,89 @@ pub fn collect_diagnostics<'a>(
 ) -> Result<impl Iterator<Item = Diagnostic> + use<'a>, ResponseError> {
     let document = server.state.get_document(document_uri)?;
     let unused_prefixes = unused_prefix(server, document)?;
-    Ok(unused_prefixes
+    Ok(syntax_errors(server, document)?
+        .chain(unused_prefixes)
         .chain(undeclared_prefix(server, document)?)
-        .chain(uncompacted_uris(server, document)?))
+        .chain(uncompacted_uris(server, document)?)
+        .chain(unknown_terms(server, document)?)
+        .chain(unbound_variables(server, document)?))
+}
+
+/// Collects the diagnostics of the document and sends them to the client
+/// (`textDocument/publishDiagnostics`), tagged with the current document version.
+pub(crate) fn publish_diagnostics(server: &Server, document_uri: &str) {
+    if server.state.status != ServerStatus::Running {
+        return;
+    }
+    let version = server
+        .state
+        .get_document(document_uri)
+        .ok()
+        .map(|document| document.version());
+    match collect_diagnostics(server, document_uri) {
+        Ok(diagnostics) => {
+            send_diagnostics(server, document_uri, version, diagnostics.collect());
+        }
+        Err(error) => {
+            error!(
+                "Error occured while publishing diagnostics:\n\n{}",
+                error.message
+            )
+        }
+    }
+}
+
+/// Publishes the diagnostics of all changed documents that did not change for the
+/// configured delay (`diagnostics.debounce_ms`).
+///
+/// Changes only mark a document as pending and postpone its diagnostics, so a burst
+/// of changes publishes them once.
+pub(crate) fn publish_due_diagnostics(server: &mut Server) {
+    for document_uri in server.state.take_due_diagnostics(now()) {
+        publish_diagnostics(server, &document_uri);
+    }
+}
+
+/// Removes all diagnostics of a document on the client, e.g. after it was closed.
+pub(crate) fn clear_diagnostics(server: &Server, document_uri: &str) {
+    send_diagnostics(server, document_uri, None, vec![]);
+}
+
+fn send_diagnostics(
+    server: &Server,
+    document_uri: &str,
+    version: Option<u32>,
+    diagnostics: Vec<Diagnostic>,
+) {
+    let diagnostic_notification = PublishDiagnosticsNotification {
+        base: NotificationMessageBase::new("textDocument/publishDiagnostics"),
+        params: PublishDiagnosticsPrarams {
+            uri: document_uri.to_string(),
+            version,
+            diagnostics,
+        },
+    };
+    match serde_json::to_string(&diagnostic_notification) {
+        Ok(message) => server.send_message(message),
+        Err(error) => error!("Could not serialize diagnostics notification:\n{}", error),
+    }
+}
+
+fn syntax_errors(
+    server: &Server,
+    document: &TextDocumentItem,
+) -> Result<impl Iterator<Item = Diagnostic>, ResponseError> {
+    let syntax_errors = get_syntax_errors(&server.state, &document.uri)?;
+    Ok(syntax_errors
+        .into_iter()
+        .map(|(message, range)| Diagnostic {
+            range,
+            severity: DiagnosticSeverity::Error,
+            source: Some("qlue-ls (syntax-error)".to_string()),
+            code: Some(DiagnosticCode::String("syntax-error".to_string())),
+            message,
+            data: None,
+        }))
 }
 
 fn unused_prefix<'a>(
@@ -85,3 +173,150 @@ fn uncompacted_uris<'a>(
     });
     Ok(diagnostics)
 }
+
+/// IRIs in the namespace of a loaded vocabulary that the vocabulary does not describe,
+/// e.g. misspelled properties.
+fn unknown_terms(
+    server: &Server,
+    document: &TextDocumentItem,
+) -> Result<impl Iterator<Item = Diagnostic>, ResponseError> {
+    let vocabulary = &server.tools.vocabulary;
+    let unknown_terms: Vec<(String, Range)> = get_resolved_iris(&server.state, &document.uri)?
+        .into_iter()
+        .filter(|(iri, _range)| vocabulary.covers_namespace(iri) && vocabulary.get(iri).is_none())
+        .collect();
+    Ok(unknown_terms.into_iter().map(|(iri, range)| Diagnostic {
+        range,
+        severity: DiagnosticSeverity::Information,
+        source: Some("qlue-ls (unknown-term)".to_string()),
+        code: Some(DiagnosticCode::String("unknown-term".to_string())),
+        message: format!("<{}> is not described by the loaded vocabularies\n", iri),
+        data: None,
+    }))
+}
+
+/// Variables of the projection or of `GROUP BY`/`ORDER BY` that the `WHERE` clause never binds,
+/// e.g. misspelled variables. The data holds the bound variable with the most similar name.
+fn unbound_variables(
+    server: &Server,
+    document: &TextDocumentItem,
+) -> Result<impl Iterator<Item = Diagnostic>, ResponseError> {
+    let unbound_variables = get_unbound_projections(&server.state, &document.uri)?;
+    Ok(unbound_variables
+        .into_iter()
+        .map(|(variable, range, closest)| Diagnostic {
+            range,
+            severity: DiagnosticSeverity::Warning,
+            source: Some("qlue-ls (unbound-variable)".to_string()),
+            code: Some(DiagnosticCode::String("unbound-variable".to_string())),
+            message: format!(
+                "'{}' is used here, but never bound in the WHERE clause\n",
+                variable
+            ),
+            data: closest.map(LSPAny::String),
+        }))
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tree_sitter::Parser;
+
+    use crate::server::{
+        lsp::{
+            base_types::LSPAny,
+            diagnostic::DiagnosticCode,
+            textdocument::{Range, TextDocumentItem},
+        },
+        state::ServerState,
+        Server,
+    };
+
+    use super::collect_diagnostics;
+
+    #[test]
+    fn unknown_terms() {
+        let text = indoc!(
+            "PREFIX ex: <http://example.org/vocab#>
+             SELECT * WHERE { ?s ex:knows ?o ; ex:knws ?x ; <http://example.org/vocab#Knows> ?y ; <http://other.org/a> ?z }"
+        );
+        let mut server = Server::new(|_message| {});
+        let mut state = ServerState::new();
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_sparql::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(text, None);
+        state.add_document(TextDocumentItem::new("uri", text), tree);
+        server.state = state;
+        server
+            .tools
+            .vocabulary
+            .add_turtle("<http://example.org/vocab#knows> a <http://www.w3.org/1999/02/22-rdf-syntax-ns#Property> .")
+            .unwrap();
+        let unknown_terms: Vec<Range> = collect_diagnostics(&server, "uri")
+            .unwrap()
+            .filter(|diagnostic| {
+                diagnostic.code == Some(DiagnosticCode::String("unknown-term".to_string()))
+            })
+            .map(|diagnostic| diagnostic.range)
+            .collect();
+        assert_eq!(
+            unknown_terms,
+            vec![Range::new(1, 34, 1, 41), Range::new(1, 47, 1, 79)]
+        );
+    }
+
+    fn unbound_variables_of(text: &str) -> Vec<(Range, Option<LSPAny>)> {
+        let mut server = Server::new(|_message| {});
+        let mut state = ServerState::new();
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_sparql::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(text, None);
+        state.add_document(TextDocumentItem::new("uri", text), tree);
+        server.state = state;
+        collect_diagnostics(&server, "uri")
+            .unwrap()
+            .filter(|diagnostic| {
+                diagnostic.code == Some(DiagnosticCode::String("unbound-variable".to_string()))
+            })
+            .map(|diagnostic| (diagnostic.range, diagnostic.data))
+            .collect()
+    }
+
+    #[test]
+    fn unbound_variables() {
+        let text = indoc!(
+            "SELECT ?nmae ?name (COUNT(?x) AS ?count) WHERE {
+               ?s <http://schema.org/name> ?name .
+               { SELECT ?s ?y WHERE { ?s ?p ?o } }
+               MINUS { ?s ?p ?z }
+             }
+             GROUP BY ?nmae ?name
+             ORDER BY ?count ?z"
+        );
+        assert_eq!(
+            unbound_variables_of(text),
+            vec![
+                (
+                    Range::new(0, 7, 0, 12),
+                    Some(LSPAny::String("?name".to_string()))
+                ),
+                (Range::new(0, 26, 0, 28), None),
+                (Range::new(2, 14, 2, 16), None),
+                (
+                    Range::new(5, 9, 5, 14),
+                    Some(LSPAny::String("?name".to_string()))
+                ),
+                (Range::new(6, 16, 6, 18), None),
+            ]
+        );
+        // NOTE: `EXISTS` groups only test the solutions, they bind nothing.
+        assert_eq!(
+            unbound_variables_of("SELECT ?x WHERE { ?s ?p ?o FILTER NOT EXISTS { ?s ?q ?x } }"),
+            vec![(Range::new(0, 7, 0, 9), None)]
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use crate::server::{
+    anaysis::get_occurrences,
+    lsp::{
+        errors::ResponseError, DocumentHighlight, DocumentHighlightKind, DocumentHighlightRequest,
+        DocumentHighlightResponse,
+    },
+    Server,
+};
+
+pub fn handle_document_highlight_request(
+    server: &mut Server,
+    request: DocumentHighlightRequest,
+) -> Result<DocumentHighlightResponse, ResponseError> {
+    let highlights: Vec<DocumentHighlight> = get_occurrences(
+        &server.state,
+        request.get_document_uri(),
+        request.get_position(),
+    )?
+    .into_iter()
+    .map(|occurrence| {
+        DocumentHighlight::new(
+            occurrence.range,
+            match occurrence.is_declaration {
+                true => DocumentHighlightKind::Write,
+                false => DocumentHighlightKind::Read,
+            },
+        )
+    })
+    .collect();
+    Ok(DocumentHighlightResponse::new(
+        request.get_id(),
+        (!highlights.is_empty()).then_some(highlights),
+    ))
+}
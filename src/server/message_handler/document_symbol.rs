@@ -0,0 +1,339 @@
+use tree_sitter::Node;
+
+use crate::server::{
+    anaysis::shorten_snippet,
+    lsp::{
+        errors::ResponseError, textdocument::Range, DocumentSymbol, DocumentSymbolRequest,
+        DocumentSymbolResponse, SymbolKind,
+    },
+    Server,
+};
+
+pub fn handle_document_symbol_request(
+    server: &mut Server,
+    request: DocumentSymbolRequest,
+) -> Result<DocumentSymbolResponse, ResponseError> {
+    let (document, tree) = server.state.get_state(request.get_document_uri())?;
+    Ok(DocumentSymbolResponse::new(
+        request.get_id(),
+        collect_symbols(tree.root_node(), &document.text),
+    ))
+}
+
+fn symbol(
+    name: &str,
+    detail: Option<String>,
+    kind: SymbolKind,
+    node: &Node,
+    selection_node: &Node,
+    children: Vec<DocumentSymbol>,
+) -> DocumentSymbol {
+    DocumentSymbol {
+        name: name.to_string(),
+        detail,
+        kind,
+        range: Range::from_node(node),
+        selection_range: Range::from_node(selection_node),
+        children,
+    }
+}
+
+fn node_text<'a>(node: &Node, text: &'a str) -> &'a str {
+    node.utf8_text(text.as_bytes()).unwrap_or("")
+}
+
+/// Collects the outline of the document below `node`.
+///
+/// The outline consists of the prologue, the query form or the update operations,
+/// subqueries and `SERVICE` blocks. Everything else is flattened.
+fn collect_symbols(node: Node, text: &str) -> Vec<DocumentSymbol> {
+    let mut cursor = node.walk();
+    node.children(&mut cursor)
+        .flat_map(|child| match child.kind() {
+            "Prologue" => prologue_symbol(&child, text).into_iter().collect(),
+            "SelectQuery" | "SubSelect" => vec![select_symbol(&child, text)],
+            "ConstructQuery" | "DescribeQuery" | "AskQuery" => vec![query_symbol(&child, text)],
+            "ServiceGraphPattern" => vec![service_symbol(&child, text)],
+            "Update1" => child
+                .named_child(0)
+                .map(|operation| update_symbol(&operation, text))
+                .into_iter()
+                .collect(),
+            _ => collect_symbols(child, text),
+        })
+        .collect()
+}
+
+fn prologue_symbol(prologue: &Node, text: &str) -> Option<DocumentSymbol> {
+    let mut cursor = prologue.walk();
+    let declarations: Vec<DocumentSymbol> = prologue
+        .named_children(&mut cursor)
+        .filter_map(|declaration| match declaration.kind() {
+            "PrefixDecl" => {
+                let namespace = declaration.child(1)?;
+                Some(symbol(
+                    node_text(&namespace, text),
+                    declaration
+                        .child(2)
+                        .map(|iri| node_text(&iri, text).to_string()),
+                    SymbolKind::Namespace,
+                    &declaration,
+                    &namespace,
+                    vec![],
+                ))
+            }
+            "BaseDecl" => {
+                let iri = declaration.child(1)?;
+                Some(symbol(
+                    "BASE",
+                    Some(node_text(&iri, text).to_string()),
+                    SymbolKind::Namespace,
+                    &declaration,
+                    &iri,
+                    vec![],
+                ))
+            }
+            _ => None,
+        })
+        .collect();
+    let first_declaration = prologue.child(0)?;
+    Some(symbol(
+        "Prologue",
+        Some(match declarations.len() {
+            1 => "1 declaration".to_string(),
+            count => format!("{} declarations", count),
+        }),
+        SymbolKind::Package,
+        prologue,
+        &first_declaration,
+        declarations,
+    ))
+}
+
+/// The projected variables of a `SelectClause`, including those bound by `(... AS ?var)`.
+fn projected_variables<'a>(select_clause: &Node<'a>) -> Vec<Node<'a>> {
+    let mut cursor = select_clause.walk();
+    let variables = select_clause
+        .named_children(&mut cursor)
+        .filter_map(|child| match child.kind() {
+            "VAR" => Some(child),
+            "assignment" => child.child_by_field_name("bound_variable"),
+            _ => None,
+        })
+        .collect();
+    variables
+}
+
+fn select_symbol(node: &Node, text: &str) -> DocumentSymbol {
+    let mut cursor = node.walk();
+    let Some(select_clause) = node
+        .children(&mut cursor)
+        .find(|child| child.kind() == "SelectClause")
+    else {
+        return symbol("SELECT", None, SymbolKind::Function, node, node, vec![]);
+    };
+    let variables = projected_variables(&select_clause);
+    let detail = match variables.is_empty() {
+        true => {
+            let mut cursor = select_clause.walk();
+            let has_wildcard = select_clause
+                .children(&mut cursor)
+                .any(|child| child.kind() == "*");
+            has_wildcard.then(|| "*".to_string())
+        }
+        false => Some(
+            variables
+                .iter()
+                .map(|variable| node_text(variable, text))
+                .collect::<Vec<_>>()
+                .join(" "),
+        ),
+    };
+    let children = variables
+        .iter()
+        .map(|variable| {
+            symbol(
+                node_text(variable, text),
+                None,
+                SymbolKind::Variable,
+                variable,
+                variable,
+                vec![],
+            )
+        })
+        .chain(collect_symbols(*node, text))
+        .collect();
+    symbol(
+        "SELECT",
+        detail,
+        SymbolKind::Function,
+        node,
+        &select_clause.child(0).unwrap_or(select_clause),
+        children,
+    )
+}
+
+fn query_symbol(node: &Node, text: &str) -> DocumentSymbol {
+    let keyword = node.child(0).unwrap_or(*node);
+    symbol(
+        keyword.kind(),
+        None,
+        SymbolKind::Function,
+        node,
+        &keyword,
+        collect_symbols(*node, text),
+    )
+}
+
+fn service_symbol(node: &Node, text: &str) -> DocumentSymbol {
+    let mut cursor = node.walk();
+    let endpoint = node
+        .named_children(&mut cursor)
+        .find(|child| child.kind() != "GroupGraphPattern")
+        .unwrap_or(*node);
+    symbol(
+        node_text(&endpoint, text),
+        Some("SERVICE".to_string()),
+        SymbolKind::Module,
+        node,
+        &endpoint,
+        collect_symbols(*node, text),
+    )
+}
+
+fn update_symbol(operation: &Node, text: &str) -> DocumentSymbol {
+    let name = match operation.kind() {
+        "InsertData" => "INSERT DATA",
+        "DeleteData" => "DELETE DATA",
+        "DeleteWhere" => "DELETE WHERE",
+        "Modify" => {
+            let mut cursor = operation.walk();
+            let clauses: Vec<&str> = operation
+                .named_children(&mut cursor)
+                .filter_map(|child| match child.kind() {
+                    "DeleteClause" => Some("DELETE"),
+                    "InsertClause" => Some("INSERT"),
+                    _ => None,
+                })
+                .collect();
+            match clauses.as_slice() {
+                ["DELETE", "INSERT"] => "DELETE/INSERT",
+                ["INSERT"] => "INSERT",
+                _ => "DELETE",
+            }
+        }
+        "Load" => "LOAD",
+        "Clear" => "CLEAR",
+        "Drop" => "DROP",
+        "Create" => "CREATE",
+        "Add" => "ADD",
+        "Move" => "MOVE",
+        "Copy" => "COPY",
+        other => other,
+    };
+    // NOTE: Graph management operations are short, their full text is a helpful detail.
+    let detail = match operation.kind() {
+        "InsertData" | "DeleteData" | "DeleteWhere" | "Modify" => None,
+        _ => Some(shorten_snippet(node_text(operation, text))),
+    };
+    symbol(
+        name,
+        detail,
+        SymbolKind::Function,
+        operation,
+        &operation.child(0).unwrap_or(*operation),
+        collect_symbols(*operation, text),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use indoc::indoc;
+    use tree_sitter::Parser;
+    use tree_sitter_sparql::LANGUAGE;
+
+    use crate::server::lsp::DocumentSymbol;
+
+    use super::collect_symbols;
+
+    fn outline(text: &str) -> Vec<String> {
+        fn flatten(symbols: &[DocumentSymbol], depth: usize, lines: &mut Vec<String>) {
+            for symbol in symbols {
+                lines.push(format!(
+                    "{}{}{}",
+                    "  ".repeat(depth),
+                    symbol.name,
+                    symbol
+                        .detail
+                        .as_ref()
+                        .map(|detail| format!(" ({})", detail))
+                        .unwrap_or_default()
+                ));
+                flatten(&symbol.children, depth + 1, lines);
+            }
+        }
+        let mut parser = Parser::new();
+        parser.set_language(&LANGUAGE.into()).unwrap();
+        let tree = parser.parse(text, None).unwrap();
+        let mut lines = vec![];
+        flatten(&collect_symbols(tree.root_node(), text), 0, &mut lines);
+        lines
+    }
+
+    #[test]
+    fn document_symbols_query() {
+        assert_eq!(
+            outline(indoc!(
+                "PREFIX wd: <http://www.wikidata.org/entity/>
+                 PREFIX wdt: <http://www.wikidata.org/prop/direct/>
+                 SELECT ?item (COUNT(?x) AS ?count) WHERE {
+                   { SELECT * WHERE { ?item wdt:P31 wd:Q5 } }
+                   UNION
+                   {
+                     SERVICE <https://qlever.cs.uni-freiburg.de/api/wikidata> {
+                       ?item wdt:P279 ?x
+                     }
+                   }
+                 }"
+            )),
+            vec![
+                "Prologue (2 declarations)",
+                "  wd: (<http://www.wikidata.org/entity/>)",
+                "  wdt: (<http://www.wikidata.org/prop/direct/>)",
+                "SELECT (?item ?count)",
+                "  ?item",
+                "  ?count",
+                "  SELECT (*)",
+                "  <https://qlever.cs.uni-freiburg.de/api/wikidata> (SERVICE)",
+            ]
+        );
+    }
+
+    #[test]
+    fn document_symbols_query_forms() {
+        assert_eq!(outline("ASK { ?s ?p ?o }"), vec!["ASK"]);
+        assert_eq!(
+            outline("CONSTRUCT { ?s ?p ?o } WHERE { SERVICE ?endpoint { ?s ?p ?o } }"),
+            vec!["CONSTRUCT", "  ?endpoint (SERVICE)"]
+        );
+    }
+
+    #[test]
+    fn document_symbols_update() {
+        assert_eq!(
+            outline(indoc!(
+                "PREFIX ex: <http://example.org/>
+                 INSERT DATA { ex:a ex:b ex:c } ;
+                 DELETE { ?s ?p ?o } INSERT { ?s ?p 1 } WHERE { ?s ?p ?o } ;
+                 CLEAR   GRAPH ex:g"
+            )),
+            vec![
+                "Prologue (1 declaration)",
+                "  ex: (<http://example.org/>)",
+                "INSERT DATA",
+                "DELETE/INSERT",
+                "CLEAR (CLEAR GRAPH ex:g)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use tree_sitter::Node;
+
+use crate::server::{
+    lsp::{
+        errors::ResponseError, FoldingRange, FoldingRangeKind, FoldingRangeRequest,
+        FoldingRangeResponse,
+    },
+    Server,
+};
+
+use super::formatting::INC_INDENTATION;
+
+pub fn handle_folding_range_request(
+    server: &mut Server,
+    request: FoldingRangeRequest,
+) -> Result<FoldingRangeResponse, ResponseError> {
+    let tree = server.state.get_tree(request.get_document_uri())?;
+    Ok(FoldingRangeResponse::new(
+        request.get_id(),
+        collect_folding_ranges(tree.root_node()),
+    ))
+}
+
+/// Collects the folding ranges of a document.
+///
+/// Blocks are the nodes the formatter indents, plus `VALUES` blocks.
+/// The prologue and runs of consecutive comment lines fold as well.
+fn collect_folding_ranges(root: Node) -> Vec<FoldingRange> {
+    let mut folding_ranges = vec![];
+    let mut comments = vec![];
+    collect_block_folding_ranges(root, &mut folding_ranges, &mut comments);
+    folding_ranges.extend(comment_folding_ranges(&comments));
+    folding_ranges.sort_by_key(|folding_range| (folding_range.start_line, folding_range.end_line));
+    folding_ranges
+}
+
+fn collect_block_folding_ranges<'a>(
+    node: Node<'a>,
+    folding_ranges: &mut Vec<FoldingRange>,
+    comments: &mut Vec<Node<'a>>,
+) {
+    let start_line = node.start_position().row as u32;
+    let end_line = node.end_position().row as u32;
+    match node.kind() {
+        "comment" => comments.push(node),
+        "Prologue" if end_line > start_line => folding_ranges.push(FoldingRange::new(
+            start_line,
+            end_line,
+            Some(FoldingRangeKind::Imports),
+        )),
+        kind if INC_INDENTATION.contains(kind) || matches!(kind, "InlineData" | "ValuesClause") => {
+            // NOTE: Keep the closing bracket visible.
+            // `VALUES` blocks end in their `DataBlock`, that ends in the bracket.
+            let block = match kind {
+                "InlineData" | "ValuesClause" => last_child(node).unwrap_or(node),
+                _ => node,
+            };
+            let end_line = match last_child(block).map(|last_child| last_child.kind()) {
+                Some("}" | "]" | ")") => end_line.saturating_sub(1),
+                _ => end_line,
+            };
+            if end_line > start_line {
+                folding_ranges.push(FoldingRange::new(start_line, end_line, None));
+            }
+        }
+        _ => {}
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_block_folding_ranges(child, folding_ranges, comments);
+    }
+}
+
+fn last_child(node: Node) -> Option<Node> {
+    node.child(node.child_count().checked_sub(1)?)
+}
+
+/// Groups comments on consecutive lines into one folding range each.
+fn comment_folding_ranges(comments: &[Node]) -> Vec<FoldingRange> {
+    let mut sorted_lines: Vec<u32> = comments
+        .iter()
+        .map(|comment| comment.start_position().row as u32)
+        .collect();
+    sorted_lines.sort();
+    let mut folding_ranges = vec![];
+    let mut lines = sorted_lines.into_iter();
+    let Some(mut run_start) = lines.next() else {
+        return folding_ranges;
+    };
+    let mut run_end = run_start;
+    for line in lines {
+        if line != run_end + 1 {
+            if run_end > run_start {
+                folding_ranges.push(FoldingRange::new(
+                    run_start,
+                    run_end,
+                    Some(FoldingRangeKind::Comment),
+                ));
+            }
+            run_start = line;
+        }
+        run_end = line;
+    }
+    if run_end > run_start {
+        folding_ranges.push(FoldingRange::new(
+            run_start,
+            run_end,
+            Some(FoldingRangeKind::Comment),
+        ));
+    }
+    folding_ranges
+}
+
+#[cfg(test)]
+mod test {
+    use indoc::indoc;
+    use tree_sitter::Parser;
+    use tree_sitter_sparql::LANGUAGE;
+
+    use crate::server::lsp::{FoldingRange, FoldingRangeKind};
+
+    use super::collect_folding_ranges;
+
+    fn folding_ranges(text: &str) -> Vec<FoldingRange> {
+        let mut parser = Parser::new();
+        parser.set_language(&LANGUAGE.into()).unwrap();
+        let tree = parser.parse(text, None).unwrap();
+        collect_folding_ranges(tree.root_node())
+    }
+
+    #[test]
+    fn fold_query() {
+        assert_eq!(
+            folding_ranges(indoc!(
+                "PREFIX wd: <http://www.wikidata.org/entity/>
+                 PREFIX wdt: <http://www.wikidata.org/prop/direct/>
+                 # first
+                 # second
+                 SELECT * WHERE {
+                   ?s wdt:P31 wd:Q5 .
+                   VALUES ?o {
+                     1
+                     2
+                   }
+                   OPTIONAL { ?s ?p ?o }
+                   # single
+                 }"
+            )),
+            vec![
+                FoldingRange::new(0, 1, Some(FoldingRangeKind::Imports)),
+                FoldingRange::new(2, 3, Some(FoldingRangeKind::Comment)),
+                FoldingRange::new(4, 11, None),
+                FoldingRange::new(6, 8, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn fold_update() {
+        assert_eq!(
+            folding_ranges(indoc!(
+                "INSERT DATA {
+                   <a> <b> <c>
+                 } ;
+                 CLEAR ALL"
+            )),
+            vec![FoldingRange::new(0, 1, None)]
+        );
+    }
+
+    #[test]
+    fn fold_construct() {
+        assert_eq!(
+            folding_ranges(indoc!(
+                "CONSTRUCT {
+                   ?s ?p ?o
+                 }
+                 WHERE {
+                   ?s ?p ?o
+                 }"
+            )),
+            vec![FoldingRange::new(0, 1, None), FoldingRange::new(3, 4, None)]
+        );
+    }
+}
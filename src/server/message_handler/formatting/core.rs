@@ -18,6 +18,7 @@ use super::utils::KEYWORDS;
 #[derive(Debug)]
 struct CommentMarker {
     text: String,
+    range: Range,
     position: Position,
     indentation_level: usize,
     trailing: bool,
@@ -100,9 +101,15 @@ impl CommentMarker {
     }
 }
 
+/// Computes the edits that format the document.
+///
+/// If a `target` node is given, only edits inside of that node are returned.
+/// The edits are still computed with the whole document as context,
+/// so the indentation of the target stays consistent with the rest of the document.
 pub(super) fn format_document(
     document: &TextDocumentItem,
     tree: &Tree,
+    target: Option<Node>,
     options: &FormattingOptions,
     settings: &FormatSettings,
 ) -> Result<Vec<TextEdit>, ResponseError> {
@@ -120,6 +127,9 @@ pub(super) fn format_document(
         "",
         settings,
     );
+    if let Some(target) = target {
+        (edits, comments) = restrict_to_target(edits, comments, &Range::from_node(&target));
+    }
     edits.sort_by(|a, b| b.range.start.cmp(&a.range.start));
 
     comments.sort_by(|a, b| a.position.cmp(&b.position));
@@ -137,6 +147,37 @@ pub(super) fn format_document(
     return Ok(edits);
 }
 
+/// Keeps only the edits and comments inside of `target`.
+///
+/// Comments are removed by the edits that surround them and inserted again afterwards.
+/// Edits removing a comment that will not be inserted again are dropped as well.
+fn restrict_to_target(
+    edits: Vec<TextEdit>,
+    comments: Vec<CommentMarker>,
+    target: &Range,
+) -> (Vec<TextEdit>, Vec<CommentMarker>) {
+    let (comments, outside_comments): (Vec<CommentMarker>, Vec<CommentMarker>) =
+        comments.into_iter().partition(|comment| {
+            target.contains(&comment.range)
+                && target.start <= comment.position
+                && comment.position <= target.end
+        });
+    let edits = edits
+        .into_iter()
+        .filter(|edit| {
+            // NOTE: Insertions at the border of the target separate it from its siblings.
+            let is_border_insertion = edit.range.is_empty()
+                && (edit.range.start == target.start || edit.range.end == target.end);
+            target.contains(&edit.range)
+                && !is_border_insertion
+                && !outside_comments
+                    .iter()
+                    .any(|comment| comment.range.overlaps(&edit.range))
+        })
+        .collect();
+    (edits, comments)
+}
+
 fn merge_comments(
     edits: Vec<ConsolidatedTextEdit>,
     comments: Vec<CommentMarker>,
@@ -285,7 +326,7 @@ lazy_static! {
     static ref BRACKETS_CLOSE: HashSet<&'static str> = HashSet::from(["]", ")", "}"]);
     static ref EXIT_EARLY: HashSet<&'static str> =
         HashSet::from(["STRING_LITERAL", "PN_LOCAL", ":"]);
-    static ref INC_INDENTATION: HashSet<&'static str> = HashSet::from([
+    pub(crate) static ref INC_INDENTATION: HashSet<&'static str> = HashSet::from([
         "BlankNodePropertyListPath",
         "GroupGraphPattern",
         "TriplesTemplateBlock",
@@ -388,6 +429,7 @@ fn comment_marker(comment_node: &Node, text: &String, indentation: usize) -> Com
             .utf8_text(text.as_bytes())
             .expect("TSNode range should have a valid utf8 string")
             .to_string(),
+        range: Range::from_node(comment_node),
         position: match attach.kind() {
             "unit" => Position::new(0, 0),
             _ => Position::from_point(attach.end_position()),
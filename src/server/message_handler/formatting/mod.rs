@@ -2,14 +2,19 @@ mod core;
 mod utils;
 use core::*;
 
-use tree_sitter::Parser;
+pub(super) use core::INC_INDENTATION;
+pub(super) use utils::KEYWORDS;
+
+use tree_sitter::{Node, Parser, Point, Tree};
 use wasm_bindgen::prelude::wasm_bindgen;
 
 use crate::server::{
     configuration::Settings,
     lsp::{
-        errors::ResponseError, textdocument::TextDocumentItem, FormattingOptions,
-        FormattingRequest, FormattingResponse,
+        errors::ResponseError,
+        textdocument::{Position, Range, TextDocumentItem},
+        FormattingOptions, FormattingRequest, FormattingResponse, OnTypeFormattingRequest,
+        RangeFormattingRequest,
     },
     Server,
 };
@@ -22,12 +27,78 @@ pub(super) fn handle_format_request(
     let edits = format_document(
         &document,
         tree,
+        None,
         request.get_options(),
         &server.settings.format,
     )?;
     Ok(FormattingResponse::new(request.get_id(), edits))
 }
 
+pub(super) fn handle_range_format_request(
+    server: &mut Server,
+    request: RangeFormattingRequest,
+) -> Result<FormattingResponse, ResponseError> {
+    let (document, tree) = server.state.get_state(request.get_document_uri())?;
+    let edits = match range_format_target(tree, request.get_range()) {
+        Some(target) => format_document(
+            document,
+            tree,
+            Some(target),
+            request.get_options(),
+            &server.settings.format,
+        )?,
+        None => vec![],
+    };
+    Ok(FormattingResponse::new(request.get_id(), edits))
+}
+
+pub(super) fn handle_on_type_format_request(
+    server: &mut Server,
+    request: OnTypeFormattingRequest,
+) -> Result<FormattingResponse, ResponseError> {
+    let (document, tree) = server.state.get_state(request.get_document_uri())?;
+    let edits = match on_type_format_target(
+        tree,
+        request.get_position(),
+        request.get_trigger_character(),
+    ) {
+        Some(target) => format_document(
+            document,
+            tree,
+            Some(target),
+            request.get_options(),
+            &server.settings.format,
+        )?,
+        None => vec![],
+    };
+    Ok(FormattingResponse::new(request.get_id(), edits))
+}
+
+/// The smallest node that encloses the selected range.
+fn range_format_target<'a>(tree: &'a Tree, range: &Range) -> Option<Node<'a>> {
+    tree.root_node()
+        .named_descendant_for_point_range(range.start.to_point(), range.end.to_point())
+}
+
+/// The node to format after `trigger` was typed right before `position`:
+/// the block closed by `}` or the triples terminated by `.` or `;`.
+fn on_type_format_target<'a>(
+    tree: &'a Tree,
+    position: &Position,
+    trigger: &str,
+) -> Option<Node<'a>> {
+    let end = position.to_point();
+    let start = Point {
+        row: end.row,
+        column: end.column.checked_sub(1)?,
+    };
+    let token = tree.root_node().descendant_for_point_range(start, end)?;
+    match token.kind() {
+        "}" | "." | ";" if token.kind() == trigger => token.parent(),
+        _ => None,
+    }
+}
+
 #[wasm_bindgen]
 pub fn format_raw(text: String) -> Result<String, String> {
     let mut parser = Parser::new();
@@ -41,6 +112,7 @@ pub fn format_raw(text: String) -> Result<String, String> {
             let edits = format_document(
                 &document,
                 &tree,
+                None,
                 &FormattingOptions {
                     tab_size: 2,
                     insert_spaces: true,
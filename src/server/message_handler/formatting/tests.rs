@@ -1,13 +1,13 @@
 use indoc::indoc;
-use tree_sitter::Parser;
+use tree_sitter::{Node, Parser, Tree};
 
 use crate::server::{
     configuration::FormatSettings,
     lsp::{
-        textdocument::{TextDocumentItem, TextEdit},
+        textdocument::{Position, Range, TextDocumentItem, TextEdit},
         FormattingOptions,
     },
-    message_handler::formatting::format_document,
+    message_handler::formatting::{format_document, on_type_format_target, range_format_target},
 };
 
 fn check_collision(edits: &Vec<TextEdit>) {
@@ -34,7 +34,7 @@ fn format_and_compare(ugly_query: &str, pretty_query: &str, format_settings: &Fo
     let tree = parser
         .parse(ugly_query.as_bytes(), None)
         .expect("could not parse");
-    let edits = format_document(&document, &tree, &format_options, format_settings).unwrap();
+    let edits = format_document(&document, &tree, None, &format_options, format_settings).unwrap();
     check_collision(&edits);
     document.apply_text_edits(edits);
     assert_eq!(document.text, pretty_query);
@@ -1264,3 +1264,142 @@ fn format_comments_2() {
     );
     format_and_compare(ugly_query, pretty_query, &FormatSettings::default());
 }
+
+fn format_target_and_compare(
+    ugly_query: &str,
+    target: impl for<'a> Fn(&'a Tree) -> Option<Node<'a>>,
+    pretty_query: &str,
+) {
+    let format_options = FormattingOptions {
+        tab_size: 2,
+        insert_spaces: true,
+    };
+    let mut parser = Parser::new();
+    parser
+        .set_language(&tree_sitter_sparql::LANGUAGE.into())
+        .unwrap();
+    let mut document = TextDocumentItem::new("testdocument", ugly_query);
+    let tree = parser
+        .parse(ugly_query.as_bytes(), None)
+        .expect("could not parse");
+    let edits = format_document(
+        &document,
+        &tree,
+        Some(target(&tree).expect("there should be a target node")),
+        &format_options,
+        &FormatSettings::default(),
+    )
+    .unwrap();
+    check_collision(&edits);
+    document.apply_text_edits(edits);
+    assert_eq!(document.text, pretty_query);
+}
+
+#[test]
+fn format_range() {
+    let ugly_query = indoc!(
+        "select * where {
+           ?a ?b ?c .
+               ?d   ?e ?f .
+           OPTIONAL {?g ?h ?i}
+         }
+        "
+    );
+    let pretty_query = indoc!(
+        "select * where {
+           ?a ?b ?c .
+           ?d ?e ?f .
+           OPTIONAL {?g ?h ?i}
+         }
+        "
+    );
+    format_target_and_compare(
+        ugly_query,
+        |tree| range_format_target(tree, &Range::new(2, 0, 2, 18)),
+        pretty_query,
+    );
+}
+
+#[test]
+fn format_range_with_comment() {
+    let ugly_query = indoc!(
+        "select * where {
+           OPTIONAL {?g ?h ?i # comment
+           }
+         }
+        "
+    );
+    let pretty_query = indoc!(
+        "select * where {
+           OPTIONAL {
+             ?g ?h ?i # comment
+           }
+         }
+        "
+    );
+    format_target_and_compare(
+        ugly_query,
+        |tree| range_format_target(tree, &Range::new(1, 2, 1, 30)),
+        pretty_query,
+    );
+}
+
+#[test]
+fn format_on_type_closing_bracket() {
+    let ugly_query = indoc!(
+        "select * where {
+           OPTIONAL {
+           ?g   ?h ?i
+               }
+         }
+        "
+    );
+    let pretty_query = indoc!(
+        "select * where {
+           OPTIONAL {
+             ?g ?h ?i
+           }
+         }
+        "
+    );
+    format_target_and_compare(
+        ugly_query,
+        |tree| on_type_format_target(tree, &Position::new(3, 7), "}"),
+        pretty_query,
+    );
+}
+
+#[test]
+fn format_on_type_dot() {
+    let ugly_query = indoc!(
+        "select * where {
+           ?a ?b ?c .   ?d
+           ?e   ?f .
+         }
+        "
+    );
+    let pretty_query = indoc!(
+        "select * where {
+           ?a ?b ?c .
+           ?d ?e ?f .
+         }
+        "
+    );
+    format_target_and_compare(
+        ugly_query,
+        |tree| on_type_format_target(tree, &Position::new(2, 11), "."),
+        pretty_query,
+    );
+}
+
+#[test]
+fn format_on_type_wrong_trigger() {
+    let mut parser = Parser::new();
+    parser
+        .set_language(&tree_sitter_sparql::LANGUAGE.into())
+        .unwrap();
+    let tree = parser.parse("SELECT * { ?a ?b ?c . }", None).unwrap();
+    assert!(on_type_format_target(&tree, &Position::new(0, 21), "}").is_none());
+    assert!(on_type_format_target(&tree, &Position::new(0, 21), ".").is_some());
+    assert!(on_type_format_target(&tree, &Position::new(0, 0), ".").is_none());
+}
@@ -0,0 +1,666 @@
+use tree_sitter::Node;
+
+use crate::server::message_handler::builtins::{find_builtin_function, ParameterKind};
+
+const QUERY_SPECIFICATION: &str = "https://www.w3.org/TR/sparql11-query/";
+const UPDATE_SPECIFICATION: &str = "https://www.w3.org/TR/sparql11-update/";
+const FEDERATED_QUERY_SPECIFICATION: &str = "https://www.w3.org/TR/sparql11-federated-query/";
+
+struct KeywordDocumentation {
+    keyword: &'static str,
+    description: &'static str,
+    example: Option<&'static str>,
+    /// The specification and the anchor of the section that defines the keyword.
+    specification: (&'static str, &'static str),
+}
+
+const KEYWORD_DOCUMENTATION: [KeywordDocumentation; 55] = [
+    // Prologue
+    KeywordDocumentation {
+        keyword: "BASE",
+        description: "The `BASE` keyword defines the base IRI, relative IRIs are resolved against it.",
+        example: Some(
+            r#"BASE <http://example.org/>
+
+SELECT * WHERE {
+  <alice> ?p ?o .
+}"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "relIRIs"),
+    },
+    KeywordDocumentation {
+        keyword: "PREFIX",
+        description: "The `PREFIX` keyword defines a namespace prefix to simplify the use of URIs in the query.",
+        example: Some(
+            r#"PREFIX foaf: <http://xmlns.com/foaf/0.1/>
+
+SELECT ?name
+WHERE {
+  ?person foaf:name ?name .
+}"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "prefNames"),
+    },
+    // Query forms
+    KeywordDocumentation {
+        keyword: "SELECT",
+        description: "The `SELECT` query form returns the bindings of the projected variables (or of all variables with `*`).",
+        example: Some(
+            r#"SELECT ?name (STRLEN(?name) AS ?length) WHERE {
+  ?person foaf:name ?name .
+}"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "select"),
+    },
+    KeywordDocumentation {
+        keyword: "CONSTRUCT",
+        description: "The `CONSTRUCT` query form returns an RDF graph, built by instantiating the template with each solution.",
+        example: Some(
+            r#"CONSTRUCT {
+  ?person vcard:FN ?name .
+}
+WHERE {
+  ?person foaf:name ?name .
+}"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "construct"),
+    },
+    KeywordDocumentation {
+        keyword: "DESCRIBE",
+        description: "The `DESCRIBE` query form returns an RDF graph that describes the resources. The content of the description is determined by the endpoint.",
+        example: Some(
+            r#"DESCRIBE ?person WHERE {
+  ?person foaf:name "Alice" .
+}"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "describe"),
+    },
+    KeywordDocumentation {
+        keyword: "ASK",
+        description: "The `ASK` query form returns true if the pattern has at least one solution.",
+        example: Some(
+            r#"ASK {
+  ?person foaf:name "Alice" .
+}"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "ask"),
+    },
+    // Select clause and dataset
+    KeywordDocumentation {
+        keyword: "DISTINCT",
+        description: "The `DISTINCT` modifier removes duplicate solutions. In an aggregate it removes duplicate values before aggregating.",
+        example: Some(
+            r#"SELECT DISTINCT ?type WHERE {
+  ?s a ?type .
+}"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "modDuplicates"),
+    },
+    KeywordDocumentation {
+        keyword: "REDUCED",
+        description: "The `REDUCED` modifier permits, but does not require, the elimination of duplicate solutions.",
+        example: None,
+        specification: (QUERY_SPECIFICATION, "modDuplicates"),
+    },
+    KeywordDocumentation {
+        keyword: "AS",
+        description: "The `AS` keyword binds the value of an expression to a new variable.",
+        example: Some(
+            r#"SELECT (COUNT(?s) AS ?count) WHERE {
+  ?s ?p ?o .
+}"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "selectExpressions"),
+    },
+    KeywordDocumentation {
+        keyword: "FROM",
+        description: "The `FROM` clause adds a graph to the default graph of the dataset.",
+        example: Some(
+            r#"SELECT * FROM <http://example.org/graph> WHERE {
+  ?s ?p ?o .
+}"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "unnamedGraph"),
+    },
+    KeywordDocumentation {
+        keyword: "FROM NAMED",
+        description: "The `FROM NAMED` clause adds a named graph to the dataset, it can be matched with `GRAPH`.",
+        example: Some(
+            r#"SELECT * FROM NAMED <http://example.org/graph> WHERE {
+  GRAPH ?g { ?s ?p ?o }
+}"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "namedGraphs"),
+    },
+    KeywordDocumentation {
+        keyword: "WHERE",
+        description: "The `WHERE` clause contains the graph pattern that is matched against the data.",
+        example: None,
+        specification: (QUERY_SPECIFICATION, "GraphPattern"),
+    },
+    // Solution modifiers
+    KeywordDocumentation {
+        keyword: "GROUP BY",
+        description: "The `GROUP BY` clause groups the solutions by the values of the expressions. Aggregates are computed per group.",
+        example: Some(
+            r#"SELECT ?type (COUNT(?s) AS ?count) WHERE {
+  ?s a ?type .
+}
+GROUP BY ?type"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "groupby"),
+    },
+    KeywordDocumentation {
+        keyword: "HAVING",
+        description: "The `HAVING` clause filters the groups.",
+        example: Some(
+            r#"SELECT ?type WHERE {
+  ?s a ?type .
+}
+GROUP BY ?type
+HAVING (COUNT(?s) > 10)"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "having"),
+    },
+    KeywordDocumentation {
+        keyword: "ORDER BY",
+        description: "The `ORDER BY` clause sorts the solutions.",
+        example: Some(
+            r#"SELECT ?name WHERE {
+  ?person foaf:name ?name .
+}
+ORDER BY DESC(?name)"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "modOrderBy"),
+    },
+    KeywordDocumentation {
+        keyword: "ASC",
+        description: "Sorts ascending (the default order).",
+        example: None,
+        specification: (QUERY_SPECIFICATION, "modOrderBy"),
+    },
+    KeywordDocumentation {
+        keyword: "DESC",
+        description: "Sorts descending.",
+        example: None,
+        specification: (QUERY_SPECIFICATION, "modOrderBy"),
+    },
+    KeywordDocumentation {
+        keyword: "LIMIT",
+        description: "The `LIMIT` clause puts an upper bound on the number of solutions.",
+        example: Some(
+            r#"SELECT * WHERE {
+  ?s ?p ?o .
+}
+LIMIT 10"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "modResultLimit"),
+    },
+    KeywordDocumentation {
+        keyword: "OFFSET",
+        description: "The `OFFSET` clause skips the first solutions. Use it together with `ORDER BY` for stable pages.",
+        example: Some(
+            r#"SELECT * WHERE {
+  ?s ?p ?o .
+}
+ORDER BY ?s
+LIMIT 10
+OFFSET 20"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "modOffset"),
+    },
+    // Graph patterns
+    KeywordDocumentation {
+        keyword: "FILTER",
+        description: "The `FILTER` keyword is used to restrict the results by applying a boolean condition.",
+        example: Some(
+            r#"SELECT ?name WHERE {
+  ?person foaf:name ?name .
+  ?person foaf:age ?age .
+  FILTER (?age > 20)
+}"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "termConstraint"),
+    },
+    KeywordDocumentation {
+        keyword: "BIND",
+        description: "The `BIND` keyword binds the value of an expression to a new variable.",
+        example: Some(
+            r#"SELECT * WHERE {
+  ?person foaf:name ?name .
+  BIND (UCASE(?name) AS ?upper)
+}"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "bind"),
+    },
+    KeywordDocumentation {
+        keyword: "VALUES",
+        description: "The `VALUES` keyword provides inline data, that is joined with the rest of the pattern.",
+        example: Some(
+            r#"SELECT * WHERE {
+  VALUES ?name { "Alice" "Bob" }
+  ?person foaf:name ?name .
+}"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "inline-data"),
+    },
+    KeywordDocumentation {
+        keyword: "UNDEF",
+        description: "Leaves the variable unbound in a row of `VALUES`.",
+        example: None,
+        specification: (QUERY_SPECIFICATION, "inline-data"),
+    },
+    KeywordDocumentation {
+        keyword: "OPTIONAL",
+        description: "The `OPTIONAL` keyword extends the solutions with the pattern if it matches, and keeps them unchanged otherwise.",
+        example: Some(
+            r#"SELECT * WHERE {
+  ?person foaf:name ?name .
+  OPTIONAL { ?person foaf:mbox ?mbox }
+}"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "optionals"),
+    },
+    KeywordDocumentation {
+        keyword: "UNION",
+        description: "The `UNION` keyword combines the solutions of two patterns.",
+        example: Some(
+            r#"SELECT * WHERE {
+  { ?book dc10:title ?title }
+  UNION
+  { ?book dc11:title ?title }
+}"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "alternatives"),
+    },
+    KeywordDocumentation {
+        keyword: "MINUS",
+        description: "The `MINUS` keyword removes the solutions that are compatible with a solution of the pattern.",
+        example: Some(
+            r#"SELECT * WHERE {
+  ?person a foaf:Person .
+  MINUS { ?person foaf:name "Bob" }
+}"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "neg-minus"),
+    },
+    KeywordDocumentation {
+        keyword: "GRAPH",
+        description: "The `GRAPH` keyword matches the pattern against a named graph.",
+        example: Some(
+            r#"SELECT * WHERE {
+  GRAPH ?g { ?s ?p ?o }
+}"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "queryDataset"),
+    },
+    KeywordDocumentation {
+        keyword: "SERVICE",
+        description: "The `SERVICE` keyword sends the pattern to a remote SPARQL endpoint (federated query).",
+        example: Some(
+            r#"SELECT * WHERE {
+  SERVICE <https://query.wikidata.org/sparql> {
+    ?item wdt:P31 wd:Q5 .
+  }
+}"#,
+        ),
+        specification: (FEDERATED_QUERY_SPECIFICATION, "introduction"),
+    },
+    KeywordDocumentation {
+        keyword: "SILENT",
+        description: "Ignores errors: a failing `SERVICE` yields a single empty solution, a failing update operation is skipped.",
+        example: None,
+        specification: (FEDERATED_QUERY_SPECIFICATION, "serviceFailure"),
+    },
+    KeywordDocumentation {
+        keyword: "a",
+        description: "Shorthand for the predicate `rdf:type` (`<http://www.w3.org/1999/02/22-rdf-syntax-ns#type>`).",
+        example: Some(
+            r#"SELECT * WHERE {
+  ?person a foaf:Person .
+}"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "abbrevRdfType"),
+    },
+    // Expressions
+    KeywordDocumentation {
+        keyword: "EXISTS",
+        description: "Tests if the pattern matches, with the variables of the current solution substituted.",
+        example: Some(
+            r#"SELECT * WHERE {
+  ?person a foaf:Person .
+  FILTER EXISTS { ?person foaf:name ?name }
+}"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "neg-pattern"),
+    },
+    KeywordDocumentation {
+        keyword: "NOT EXISTS",
+        description: "Tests if the pattern does not match, with the variables of the current solution substituted.",
+        example: Some(
+            r#"SELECT * WHERE {
+  ?person a foaf:Person .
+  FILTER NOT EXISTS { ?person foaf:name ?name }
+}"#,
+        ),
+        specification: (QUERY_SPECIFICATION, "neg-pattern"),
+    },
+    KeywordDocumentation {
+        keyword: "IN",
+        description: "Tests if the value is equal to one of the values in the list.",
+        example: Some(r#"FILTER (?lang IN ("en", "de"))"#),
+        specification: (QUERY_SPECIFICATION, "func-in"),
+    },
+    KeywordDocumentation {
+        keyword: "NOT IN",
+        description: "Tests if the value is equal to none of the values in the list.",
+        example: Some(r#"FILTER (?lang NOT IN ("en", "de"))"#),
+        specification: (QUERY_SPECIFICATION, "func-not-in"),
+    },
+    KeywordDocumentation {
+        keyword: "SEPARATOR",
+        description: "The separator of the values concatenated by `GROUP_CONCAT`, a space by default.",
+        example: Some(r#"SELECT (GROUP_CONCAT(?name; SEPARATOR=", ") AS ?names) WHERE { ?person foaf:name ?name }"#),
+        specification: (QUERY_SPECIFICATION, "defn_aggGroupConcat"),
+    },
+    // Updates
+    KeywordDocumentation {
+        keyword: "INSERT DATA",
+        description: "Adds the given triples (without variables) to the graph store.",
+        example: Some(
+            r#"INSERT DATA {
+  <http://example.org/alice> foaf:name "Alice" .
+}"#,
+        ),
+        specification: (UPDATE_SPECIFICATION, "insertData"),
+    },
+    KeywordDocumentation {
+        keyword: "DELETE DATA",
+        description: "Removes the given triples (without variables) from the graph store.",
+        example: Some(
+            r#"DELETE DATA {
+  <http://example.org/alice> foaf:name "Alice" .
+}"#,
+        ),
+        specification: (UPDATE_SPECIFICATION, "deleteData"),
+    },
+    KeywordDocumentation {
+        keyword: "DELETE WHERE",
+        description: "Removes the triples that match the pattern, the pattern is also the template.",
+        example: Some(
+            r#"DELETE WHERE {
+  ?person foaf:name "Alice" .
+}"#,
+        ),
+        specification: (UPDATE_SPECIFICATION, "deleteWhere"),
+    },
+    KeywordDocumentation {
+        keyword: "INSERT",
+        description: "Adds the triples of the template, instantiated for each solution of the `WHERE` clause.",
+        example: Some(
+            r#"INSERT {
+  ?person foaf:givenName ?name .
+}
+WHERE {
+  ?person foaf:name ?name .
+}"#,
+        ),
+        specification: (UPDATE_SPECIFICATION, "deleteInsert"),
+    },
+    KeywordDocumentation {
+        keyword: "DELETE",
+        description: "Removes the triples of the template, instantiated for each solution of the `WHERE` clause.",
+        example: Some(
+            r#"DELETE {
+  ?person foaf:age ?age .
+}
+WHERE {
+  ?person foaf:age ?age .
+  FILTER (?age < 0)
+}"#,
+        ),
+        specification: (UPDATE_SPECIFICATION, "deleteInsert"),
+    },
+    KeywordDocumentation {
+        keyword: "WITH",
+        description: "The graph that is modified and matched, if no other graph is specified.",
+        example: None,
+        specification: (UPDATE_SPECIFICATION, "deleteInsert"),
+    },
+    KeywordDocumentation {
+        keyword: "USING",
+        description: "Sets the dataset of the `WHERE` clause of an update, like `FROM` in a query.",
+        example: None,
+        specification: (UPDATE_SPECIFICATION, "deleteInsert"),
+    },
+    KeywordDocumentation {
+        keyword: "USING NAMED",
+        description: "Adds a named graph to the dataset of the `WHERE` clause of an update, like `FROM NAMED` in a query.",
+        example: None,
+        specification: (UPDATE_SPECIFICATION, "deleteInsert"),
+    },
+    KeywordDocumentation {
+        keyword: "LOAD",
+        description: "Reads an RDF document and inserts its triples into the graph store.",
+        example: Some(r#"LOAD <http://example.org/data.ttl> INTO GRAPH <http://example.org/graph>"#),
+        specification: (UPDATE_SPECIFICATION, "load"),
+    },
+    KeywordDocumentation {
+        keyword: "INTO",
+        description: "The graph that `LOAD` inserts into.",
+        example: None,
+        specification: (UPDATE_SPECIFICATION, "load"),
+    },
+    KeywordDocumentation {
+        keyword: "CLEAR",
+        description: "Removes all triples of the graph(s).",
+        example: Some(r#"CLEAR GRAPH <http://example.org/graph>"#),
+        specification: (UPDATE_SPECIFICATION, "clear"),
+    },
+    KeywordDocumentation {
+        keyword: "CREATE",
+        description: "Creates a new, empty graph.",
+        example: Some(r#"CREATE GRAPH <http://example.org/graph>"#),
+        specification: (UPDATE_SPECIFICATION, "create"),
+    },
+    KeywordDocumentation {
+        keyword: "DROP",
+        description: "Removes the graph(s) from the graph store.",
+        example: Some(r#"DROP GRAPH <http://example.org/graph>"#),
+        specification: (UPDATE_SPECIFICATION, "drop"),
+    },
+    KeywordDocumentation {
+        keyword: "ADD",
+        description: "Inserts all triples of the source graph into the destination graph.",
+        example: Some(r#"ADD <http://example.org/source> TO <http://example.org/destination>"#),
+        specification: (UPDATE_SPECIFICATION, "add"),
+    },
+    KeywordDocumentation {
+        keyword: "MOVE",
+        description: "Moves all triples of the source graph into the destination graph, replacing its content. The source graph is removed.",
+        example: Some(r#"MOVE <http://example.org/source> TO <http://example.org/destination>"#),
+        specification: (UPDATE_SPECIFICATION, "move"),
+    },
+    KeywordDocumentation {
+        keyword: "COPY",
+        description: "Copies all triples of the source graph into the destination graph, replacing its content.",
+        example: Some(r#"COPY <http://example.org/source> TO <http://example.org/destination>"#),
+        specification: (UPDATE_SPECIFICATION, "copy"),
+    },
+    KeywordDocumentation {
+        keyword: "TO",
+        description: "The destination graph of `ADD`, `MOVE` or `COPY`.",
+        example: None,
+        specification: (UPDATE_SPECIFICATION, "graphManagement"),
+    },
+    KeywordDocumentation {
+        keyword: "DEFAULT",
+        description: "The default graph of the graph store.",
+        example: None,
+        specification: (UPDATE_SPECIFICATION, "graphManagement"),
+    },
+    KeywordDocumentation {
+        keyword: "NAMED",
+        description: "All named graphs of the graph store.",
+        example: None,
+        specification: (UPDATE_SPECIFICATION, "graphManagement"),
+    },
+    KeywordDocumentation {
+        keyword: "ALL",
+        description: "All graphs of the graph store, the default graph and all named graphs.",
+        example: None,
+        specification: (UPDATE_SPECIFICATION, "graphManagement"),
+    },
+];
+
+/// The keyword a token belongs to, keywords that consist of several tokens
+/// (e.g. `GROUP BY`) are documented together.
+fn keyword(node: &Node) -> String {
+    let kind = node.kind();
+    let parent_kind = node.parent().map(|parent| parent.kind()).unwrap_or("");
+    match (kind, parent_kind) {
+        ("GROUP" | "BY", "GroupClause") => "GROUP BY",
+        ("ORDER" | "BY", "OrderClause") => "ORDER BY",
+        ("NOT" | "EXISTS", "NotExistsFunc") => "NOT EXISTS",
+        ("NOT", "binary_expression") => "NOT IN",
+        ("IN", "binary_expression")
+            if node
+                .prev_sibling()
+                .is_some_and(|sibling| sibling.kind() == "NOT") =>
+        {
+            "NOT IN"
+        }
+        ("NAMED", "NamedGraphClause") => "FROM NAMED",
+        ("FROM", _)
+            if node
+                .next_sibling()
+                .is_some_and(|sibling| sibling.kind() == "NamedGraphClause") =>
+        {
+            "FROM NAMED"
+        }
+        ("NAMED", "UsingClause") => "USING NAMED",
+        ("INSERT" | "DATA", "InsertData") => "INSERT DATA",
+        ("DELETE" | "DATA", "DeleteData") => "DELETE DATA",
+        ("DELETE" | "WHERE", "DeleteWhere") => "DELETE WHERE",
+        _ => kind,
+    }
+    .to_string()
+}
+
+/// The documentation of the keyword or built-in function of a token, as markdown.
+pub(super) fn documentation(node: &Node) -> Option<String> {
+    if node.is_named() {
+        return None;
+    }
+    let keyword = keyword(node);
+    if let Some(documentation) = KEYWORD_DOCUMENTATION
+        .iter()
+        .find(|documentation| documentation.keyword == keyword)
+    {
+        let (specification, anchor) = documentation.specification;
+        return Some(format!(
+            "### **{}**\n\n{}\n\n{}[Specification]({}#{})",
+            documentation.keyword,
+            documentation.description,
+            documentation
+                .example
+                .map_or(String::new(), |example| format!(
+                    "---\n\n**Example:**\n\n```sparql\n{}\n```\n\n",
+                    example
+                )),
+            specification,
+            anchor
+        ));
+    }
+    let function = find_builtin_function(&keyword)?;
+    let parameters: String = function
+        .parameters
+        .iter()
+        .filter_map(|parameter| match parameter.kind {
+            ParameterKind::Optional => Some(format!(
+                "- `{}`: {}\n",
+                parameter.name,
+                parameter.description.unwrap_or_default()
+            )),
+            _ => None,
+        })
+        .collect();
+    Some(format!(
+        "### **{}**\n\n```sparql\n{}\n```\n\n{}\n\n{}[Specification]({}#{})",
+        function.name,
+        function.signature(),
+        function.description,
+        match parameters.is_empty() {
+            true => String::new(),
+            false => format!("{}\n", parameters),
+        },
+        QUERY_SPECIFICATION,
+        function.specification_anchor()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use tree_sitter::{Node, Parser, Point};
+
+    use super::{documentation, KEYWORD_DOCUMENTATION};
+
+    fn documentation_at(text: &str, column: usize) -> Option<String> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_sparql::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(text, None).unwrap();
+        let point = Point::new(0, column);
+        let node: Node = tree
+            .root_node()
+            .descendant_for_point_range(point, point)
+            .unwrap();
+        documentation(&node)
+    }
+
+    #[test]
+    fn keywords_are_unique() {
+        for (index, documentation) in KEYWORD_DOCUMENTATION.iter().enumerate() {
+            assert!(
+                KEYWORD_DOCUMENTATION[..index]
+                    .iter()
+                    .all(|other| other.keyword != documentation.keyword),
+                "\"{}\" is documented twice",
+                documentation.keyword
+            );
+        }
+    }
+
+    #[test]
+    fn keyword_documentation() {
+        let text = "SELECT * WHERE { ?s a ?o FILTER (?o NOT IN (1)) } GROUP BY ?s";
+        let filter = documentation_at(text, 26).unwrap();
+        assert!(filter.starts_with("### **FILTER**"));
+        assert!(filter.contains("https://www.w3.org/TR/sparql11-query/#termConstraint"));
+        assert!(documentation_at(text, 20).unwrap().contains("rdf:type"));
+        assert!(documentation_at(text, 38)
+            .unwrap()
+            .starts_with("### **NOT IN**"));
+        assert!(documentation_at(text, 41)
+            .unwrap()
+            .starts_with("### **NOT IN**"));
+        assert!(documentation_at(text, 51)
+            .unwrap()
+            .starts_with("### **GROUP BY**"));
+        assert!(documentation_at(text, 57)
+            .unwrap()
+            .starts_with("### **GROUP BY**"));
+        assert_eq!(documentation_at(text, 17), None);
+    }
+
+    #[test]
+    fn builtin_documentation() {
+        let text = "SELECT * WHERE { FILTER REGEX(?s, \"a\") }";
+        let regex = documentation_at(text, 25).unwrap();
+        assert!(regex.starts_with("### **REGEX**"));
+        assert!(regex.contains("REGEX(text, pattern, [flags]) -> xsd:boolean"));
+        assert!(regex.contains("- `flags`: Optional flags"));
+        assert!(regex.contains("https://www.w3.org/TR/sparql11-query/#func-regex"));
+    }
+}
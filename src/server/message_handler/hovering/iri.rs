@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+
+use tree_sitter::{Node, Tree};
+
+use crate::server::{
+    anaysis::{compact_with_declared_prefix, get_prefix_map, resolve_iri, strip_angle_brackets},
+    Server,
+};
+
+/// The `IRIREF` or `PrefixedName` node a node is part of.
+///
+/// IRIs of `PREFIX` and `BASE` declarations are not resolved, they are documented by the
+/// declaration itself.
+fn iri_node(node: Node) -> Option<Node> {
+    let iri = match node.kind() {
+        "IRIREF" | "PrefixedName" => node,
+        "PNAME_NS" | "PN_LOCAL" => node.parent()?,
+        "PN_PREFIX" | ":" => node.parent()?.parent()?,
+        _ => return None,
+    };
+    match iri.kind() {
+        "IRIREF"
+            if iri
+                .parent()
+                .is_some_and(|parent| matches!(parent.kind(), "PrefixDecl" | "BaseDecl")) =>
+        {
+            None
+        }
+        "IRIREF" | "PrefixedName" => Some(iri),
+        _ => None,
+    }
+}
+
+/// Writes `iri` in its compact form if there is one.
+fn display_iri(server: &Server, prefix_map: &HashMap<String, String>, iri: &str) -> String {
+    match compact_with_declared_prefix(prefix_map, iri)
+        .or_else(|| server.shorten_uri(iri).map(|(_, _, curie)| curie))
+    {
+        Some(curie) => format!("`{}`", curie),
+        None => format!("`<{}>`", iri),
+    }
+}
+
+/// The label, comment, types, domains and ranges of `iri` in the loaded vocabularies.
+fn vocabulary_metadata(server: &Server, prefix_map: &HashMap<String, String>, iri: &str) -> String {
+    let Some(term) = server.tools.vocabulary.get(iri) else {
+        return String::new();
+    };
+    let mut metadata = String::from("\n\n---");
+    if let Some(label) = term.label() {
+        metadata.push_str(&format!("\n\n**{}**", label));
+    }
+    if let Some(comment) = term.comment() {
+        metadata.push_str(&format!("\n\n{}", comment));
+    }
+    for (name, iris) in [
+        ("Type", &term.types),
+        ("Domain", &term.domains),
+        ("Range", &term.ranges),
+    ] {
+        if !iris.is_empty() {
+            let iris: Vec<String> = iris
+                .iter()
+                .map(|iri| display_iri(server, prefix_map, iri))
+                .collect();
+            metadata.push_str(&format!("\n\n**{}:** {}", name, iris.join(", ")));
+        }
+    }
+    metadata
+}
+
+/// Shows the full IRI of a prefixed name or the compact form of an IRI,
+/// followed by what the loaded vocabularies state about it.
+///
+/// Prefixes declared in the document take precedence over the known prefixes of the
+/// `uri_converter`.
+pub(super) fn iri_hover(server: &Server, tree: &Tree, node: Node, text: &str) -> Option<String> {
+    let iri_node = iri_node(node)?;
+    let written = iri_node.utf8_text(text.as_bytes()).ok()?;
+    let prefix_map = get_prefix_map(tree.root_node(), text);
+    let (iri, content) = match iri_node.kind() {
+        "PrefixedName" => match resolve_iri(&iri_node, &prefix_map, text) {
+            Some(iri) => {
+                let content = format!("`{}`\n\n**IRI:** `<{}>`", written, iri);
+                (iri, content)
+            }
+            None => {
+                let iri = server
+                    .tools
+                    .uri_converter
+                    .expand(&written.replace('\\', ""))
+                    .ok()?;
+                let namespace = iri_node.child(0)?.utf8_text(text.as_bytes()).ok()?;
+                let content = format!(
+                    "`{}`\n\n**IRI:** `<{}>`\n\n*The prefix `{}` is not declared.*",
+                    written, iri, namespace
+                );
+                (iri, content)
+            }
+        },
+        _ => {
+            let iri = strip_angle_brackets(written).to_string();
+            let compact_form = match compact_with_declared_prefix(&prefix_map, &iri) {
+                Some(curie) => format!("\n\n**Compact form:** `{}`", curie),
+                None => match server.shorten_uri(&iri) {
+                    Some((prefix, _, curie)) => format!(
+                        "\n\n**Compact form:** `{}`\n\n*The prefix `{}:` is not declared.*",
+                        curie, prefix
+                    ),
+                    None => String::new(),
+                },
+            };
+            let content = format!("`<{}>`{}", iri, compact_form);
+            (iri, content)
+        }
+    };
+    Some(content + &vocabulary_metadata(server, &prefix_map, &iri))
+}
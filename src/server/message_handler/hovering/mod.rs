@@ -0,0 +1,218 @@
+mod documentation;
+mod iri;
+mod variable;
+
+use documentation::documentation;
+use iri::iri_hover;
+use variable::variable_hover;
+
+use crate::server::{
+    anaysis::{get_node_at_position, ScopeModel},
+    lsp::{errors::ResponseError, HoverRequest, HoverResponse},
+    Server,
+};
+
+pub fn handle_hover_request(
+    server: &mut Server,
+    request: HoverRequest,
+) -> Result<HoverResponse, ResponseError> {
+    let (document, tree) = server.state.get_state(request.get_document_uri())?;
+    let content =
+        get_node_at_position(tree, &document.text, request.get_position()).and_then(|node| {
+            match node.kind() {
+                "VAR" => variable_hover(
+                    &ScopeModel::new(tree.root_node(), &document.text),
+                    node,
+                    &document.text,
+                ),
+                "IRIREF" | "PrefixedName" | "PNAME_NS" | "PN_PREFIX" | ":" | "PN_LOCAL" => {
+                    iri_hover(server, tree, node, &document.text)
+                }
+                _ => documentation(&node),
+            }
+        });
+    Ok(HoverResponse::new(request.get_id(), content))
+}
+
+#[cfg(test)]
+mod tests {
+    use curies::Converter;
+    use indoc::indoc;
+    use tree_sitter::Parser;
+
+    use crate::server::{
+        lsp::{textdocument::TextDocumentItem, HoverRequest},
+        state::ServerState,
+        Server,
+    };
+
+    use super::handle_hover_request;
+
+    fn setup_server(text: &str) -> Server {
+        let mut server = Server::new(|_message| {});
+        let mut state = ServerState::new();
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_sparql::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(text, None);
+        state.add_document(TextDocumentItem::new("uri", text), tree);
+        server.state = state;
+        let mut converter = Converter::new(":");
+        converter
+            .add_prefix("wdt", "http://www.wikidata.org/prop/direct/")
+            .unwrap();
+        server.tools.uri_converter = converter;
+        server
+    }
+
+    /// The hover content at `line` and `character` of the document.
+    fn hover_at(server: &mut Server, line: u32, character: u32) -> Option<String> {
+        let request: HoverRequest = serde_json::from_value(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "textDocument/hover",
+            "params": {
+                "textDocument": {"uri": "uri"},
+                "position": {"line": line, "character": character}
+            }
+        }))
+        .unwrap();
+        let response = handle_hover_request(server, request).unwrap();
+        serde_json::to_value(response).unwrap()["result"]["contents"]["value"]
+            .as_str()
+            .map(|value| value.to_string())
+    }
+
+    /// The hover content at `line` and `character` of `text`.
+    fn hover(text: &str, line: u32, character: u32) -> Option<String> {
+        hover_at(&mut setup_server(text), line, character)
+    }
+
+    #[test]
+    fn hover_prefixed_name() {
+        let text = indoc!(
+            "PREFIX ex: <http://example.org/>
+             SELECT * WHERE { ?s ex:knows ?o ; wdt:P31 ?c }"
+        );
+        assert_eq!(
+            hover(text, 1, 22).as_deref(),
+            Some("`ex:knows`\n\n**IRI:** `<http://example.org/knows>`")
+        );
+        assert_eq!(hover(text, 1, 20), hover(text, 1, 22));
+        assert_eq!(
+            hover(text, 1, 36).as_deref(),
+            Some(
+                "`wdt:P31`\n\n**IRI:** `<http://www.wikidata.org/prop/direct/P31>`\n\n*The prefix `wdt:` is not declared.*"
+            )
+        );
+        assert_eq!(hover(text, 0, 15), None);
+    }
+
+    #[test]
+    fn hover_iri() {
+        let text = indoc!(
+            "PREFIX ex: <http://example.org/>
+             SELECT * WHERE { ?s <http://example.org/knows> <http://www.wikidata.org/prop/direct/P31>, <http://other.org/a/> }"
+        );
+        assert_eq!(
+            hover(text, 1, 25).as_deref(),
+            Some("`<http://example.org/knows>`\n\n**Compact form:** `ex:knows`")
+        );
+        assert_eq!(
+            hover(text, 1, 50).as_deref(),
+            Some(
+                "`<http://www.wikidata.org/prop/direct/P31>`\n\n**Compact form:** `wdt:P31`\n\n*The prefix `wdt:` is not declared.*"
+            )
+        );
+        assert_eq!(
+            hover(text, 1, 95).as_deref(),
+            Some("`<http://other.org/a/>`")
+        );
+    }
+
+    #[test]
+    fn hover_variable() {
+        let text = indoc!(
+            "SELECT ?s ?label WHERE {
+               ?s ?p ?o .
+               {
+                 SELECT ?o WHERE { ?o ?q ?z }
+               }
+               BIND (STR(?o) AS ?label)
+             }"
+        );
+        assert_eq!(
+            hover(text, 1, 3).as_deref(),
+            Some(
+                "`?s`\n\nBound by:\n- triple pattern (line 2)\n\nProjected by the `SELECT` clause."
+            )
+        );
+        assert_eq!(
+            hover(text, 1, 9).as_deref(),
+            Some("`?o`\n\nBound by:\n- triple pattern (line 2)\n- subquery (line 4)\n- triple pattern (line 4)\n\nNot projected.")
+        );
+        assert_eq!(
+            hover(text, 3, 28).as_deref(),
+            Some("`?z`\n\nBound by:\n- triple pattern (line 4)\n\nNot projected.")
+        );
+        assert_eq!(
+            hover(text, 0, 11).as_deref(),
+            Some("`?label`\n\nBound by:\n- `BIND` (line 6)\n\nProjected by the `SELECT` clause.")
+        );
+        assert_eq!(
+            hover("ASK { FILTER (?x) }", 0, 15).as_deref(),
+            Some("`?x`\n\nNever bound.")
+        );
+    }
+
+    #[test]
+    fn hover_vocabulary_metadata() {
+        let text = indoc!(
+            "PREFIX ex: <http://example.org/vocab#>
+             PREFIX owl: <http://www.w3.org/2002/07/owl#>
+             SELECT * WHERE { ?s ex:knows ?o ; ex:unknown ?x }"
+        );
+        let mut server = setup_server(text);
+        server
+            .tools
+            .vocabulary
+            .add_turtle(indoc!(
+                r#"
+                @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+                @prefix owl: <http://www.w3.org/2002/07/owl#> .
+                @prefix ex: <http://example.org/vocab#> .
+                ex:knows a owl:ObjectProperty ;
+                    rdfs:label "knows"@en ;
+                    rdfs:comment "A person known by this person."@en ;
+                    rdfs:domain ex:Person ;
+                    rdfs:range <http://other.org/Agent> .
+                "#
+            ))
+            .unwrap();
+        assert_eq!(
+            hover_at(&mut server, 2, 22).as_deref(),
+            Some(indoc!(
+                "`ex:knows`
+
+                 **IRI:** `<http://example.org/vocab#knows>`
+
+                 ---
+
+                 **knows**
+
+                 A person known by this person.
+
+                 **Type:** `owl:ObjectProperty`
+
+                 **Domain:** `ex:Person`
+
+                 **Range:** `<http://other.org/Agent>`"
+            ))
+        );
+        assert_eq!(
+            hover_at(&mut server, 2, 36).as_deref(),
+            Some("`ex:unknown`\n\n**IRI:** `<http://example.org/vocab#unknown>`")
+        );
+    }
+}
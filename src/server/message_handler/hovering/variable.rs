@@ -0,0 +1,48 @@
+use tree_sitter::Node;
+
+use crate::server::anaysis::ScopeModel;
+
+/// Describes the construct that binds a variable, given the binding `VAR` node.
+fn binding_kind(binding: &Node) -> &'static str {
+    match binding.parent().map(|parent| parent.kind()) {
+        Some("assignment") => match binding
+            .parent()
+            .and_then(|assignment| assignment.parent())
+            .map(|parent| parent.kind())
+        {
+            Some("Bind") => "`BIND`",
+            Some("SelectClause") => "`SELECT` expression",
+            _ => "`GROUP BY` expression",
+        },
+        Some("DataBlock") => "`VALUES`",
+        Some("GraphGraphPattern") => "`GRAPH`",
+        Some("SelectClause") => "subquery",
+        _ => "triple pattern",
+    }
+}
+
+/// Lists where the variable at the `VAR` node gets bound and if it is projected.
+pub(super) fn variable_hover(scopes: &ScopeModel, variable: Node, text: &str) -> Option<String> {
+    let bindings: Vec<String> = scopes
+        .bindings(&variable)
+        .into_iter()
+        .map(|binding| {
+            format!(
+                "- {} (line {})",
+                binding_kind(&binding),
+                binding.start_position().row + 1
+            )
+        })
+        .collect();
+    let mut content = format!("`{}`\n\n", variable.utf8_text(text.as_bytes()).ok()?);
+    match bindings.is_empty() {
+        true => content.push_str("Never bound."),
+        false => content.push_str(&format!("Bound by:\n{}", bindings.join("\n"))),
+    }
+    match scopes.is_projected(&variable) {
+        Some(true) => content.push_str("\n\nProjected by the `SELECT` clause."),
+        Some(false) => content.push_str("\n\nNot projected."),
+        None => {}
+    }
+    Some(content)
+}
@@ -1,16 +1,29 @@
+mod builtins;
 mod code_action;
 mod commands;
 mod completion;
+mod definition;
 mod diagnostic;
+mod document_highlight;
+mod document_symbol;
+mod folding_range;
 mod formatting;
 mod hovering;
 mod lifecycle;
 mod misc;
+mod references;
+mod rename;
+mod semantic_tokens;
+mod signature_help;
 mod textdocument_syncronization;
 
 use code_action::handle_codeaction_request;
 use commands::handle_execute_command_request;
-use completion::handle_completion_request;
+use completion::{handle_completion_request, handle_completion_resolve_request};
+use definition::handle_definition_request;
+use document_highlight::handle_document_highlight_request;
+use document_symbol::handle_document_symbol_request;
+use folding_range::handle_folding_range_request;
 use hovering::handle_hover_request;
 use lifecycle::{
     handle_exit_notifcation, handle_initialize_request, handle_initialized_notifcation,
@@ -18,10 +31,15 @@ use lifecycle::{
 };
 use log::warn;
 use misc::handle_set_trace_notifcation;
+use references::handle_references_request;
+use rename::{handle_prepare_rename_request, handle_rename_request};
+use semantic_tokens::{handle_semantic_tokens_full_request, handle_semantic_tokens_range_request};
 use serde::{de::DeserializeOwned, Serialize};
+use signature_help::handle_signature_help_request;
 use std::any::type_name;
 use textdocument_syncronization::{
-    handle_did_change_notification, handle_did_open_notification, handle_did_save_notification,
+    handle_did_change_notification, handle_did_close_notification, handle_did_open_notification,
+    handle_did_save_notification,
 };
 
 pub use diagnostic::*;
@@ -29,7 +47,9 @@ pub use formatting::format_raw;
 
 use crate::server::lsp::errors::ErrorCode;
 
-use self::formatting::handle_format_request;
+use self::formatting::{
+    handle_format_request, handle_on_type_format_request, handle_range_format_request,
+};
 
 use super::{
     lsp::{
@@ -70,6 +90,8 @@ pub fn dispatch(
 ) -> Result<Option<String>, ResponseError> {
     let message = deserialize_message(message_string)?;
     let method = message.get_method().unwrap_or("");
+    // NOTE: Publish what got due while the client was busy, before its next change lands.
+    publish_due_diagnostics(server);
     macro_rules! link {
         ($handler:ident) => {
             handle_request(server, message, $handler)?
@@ -80,10 +102,23 @@ pub fn dispatch(
         "initialize" => link!(handle_initialize_request),
         "shutdown" => link!(handle_shutdown_request),
         "textDocument/formatting" => link!(handle_format_request),
+        "textDocument/rangeFormatting" => link!(handle_range_format_request),
+        "textDocument/onTypeFormatting" => link!(handle_on_type_format_request),
         "textDocument/diagnostic" => link!(handle_diagnostic_request),
         "textDocument/codeAction" => link!(handle_codeaction_request),
         "textDocument/hover" => link!(handle_hover_request),
         "textDocument/completion" => link!(handle_completion_request),
+        "completionItem/resolve" => link!(handle_completion_resolve_request),
+        "textDocument/signatureHelp" => link!(handle_signature_help_request),
+        "textDocument/definition" => link!(handle_definition_request),
+        "textDocument/references" => link!(handle_references_request),
+        "textDocument/documentHighlight" => link!(handle_document_highlight_request),
+        "textDocument/prepareRename" => link!(handle_prepare_rename_request),
+        "textDocument/rename" => link!(handle_rename_request),
+        "textDocument/documentSymbol" => link!(handle_document_symbol_request),
+        "textDocument/foldingRange" => link!(handle_folding_range_request),
+        "textDocument/semanticTokens/full" => link!(handle_semantic_tokens_full_request),
+        "textDocument/semanticTokens/range" => link!(handle_semantic_tokens_range_request),
         "workspace/executeCommand" => link!(handle_execute_command_request),
         // Notifications
         "initialized" => link!(handle_initialized_notifcation),
@@ -91,6 +126,7 @@ pub fn dispatch(
         "textDocument/didOpen" => link!(handle_did_open_notification),
         "textDocument/didChange" => link!(handle_did_change_notification),
         "textDocument/didSave" => link!(handle_did_save_notification),
+        "textDocument/didClose" => link!(handle_did_close_notification),
         "$/setTrace" => link!(handle_set_trace_notifcation),
         unknown_method => {
             warn!(
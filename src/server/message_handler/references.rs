@@ -0,0 +1,22 @@
+use crate::server::{
+    anaysis::get_occurrences,
+    lsp::{errors::ResponseError, textdocument::Location, ReferencesRequest, ReferencesResponse},
+    Server,
+};
+
+pub fn handle_references_request(
+    server: &mut Server,
+    request: ReferencesRequest,
+) -> Result<ReferencesResponse, ResponseError> {
+    let document_uri = request.get_document_uri();
+    let locations: Vec<Location> =
+        get_occurrences(&server.state, document_uri, request.get_position())?
+            .into_iter()
+            .filter(|occurrence| request.include_declaration() || !occurrence.is_declaration)
+            .map(|occurrence| Location::new(document_uri, occurrence.range))
+            .collect();
+    Ok(ReferencesResponse::new(
+        request.get_id(),
+        (!locations.is_empty()).then_some(locations),
+    ))
+}
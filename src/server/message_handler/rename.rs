@@ -0,0 +1,365 @@
+use std::collections::HashMap;
+
+use crate::server::{
+    anaysis::{
+        get_declared_prefixes, get_kind_at_position, get_occurrences, variable_name_is_taken,
+    },
+    lsp::{
+        errors::{ErrorCode, ResponseError},
+        textdocument::{Position, Range, TextEdit},
+        PrepareRenameRequest, PrepareRenameResponse, RenameRequest, RenameResponse, WorkspaceEdit,
+    },
+    Server,
+};
+
+#[derive(Debug, PartialEq)]
+enum RenameTarget {
+    Variable,
+    Prefix,
+}
+
+pub fn handle_prepare_rename_request(
+    server: &mut Server,
+    request: PrepareRenameRequest,
+) -> Result<PrepareRenameResponse, ResponseError> {
+    Ok(PrepareRenameResponse::new(
+        request.get_id(),
+        prepare_rename(server, request.get_document_uri(), request.get_position())?,
+    ))
+}
+
+pub fn handle_rename_request(
+    server: &mut Server,
+    request: RenameRequest,
+) -> Result<RenameResponse, ResponseError> {
+    let document_uri = request.get_document_uri();
+    let edits = rename(
+        server,
+        document_uri,
+        request.get_position(),
+        request.get_new_name(),
+    )?;
+    Ok(RenameResponse::new(
+        request.get_id(),
+        Some(WorkspaceEdit {
+            changes: HashMap::from([(document_uri.to_string(), edits)]),
+        }),
+    ))
+}
+
+fn rename_target(
+    server: &Server,
+    document_uri: &String,
+    position: &Position,
+) -> Result<Option<RenameTarget>, ResponseError> {
+    Ok(
+        match get_kind_at_position(&server.state, document_uri, position)? {
+            "VAR" => Some(RenameTarget::Variable),
+            "PN_PREFIX" | ":" | "PNAME_NS" => Some(RenameTarget::Prefix),
+            _ => None,
+        },
+    )
+}
+
+/// Returns the range of the name inside an occurrence,
+/// that is without the `?` of a variable or the `:` of a prefix.
+fn name_range(target: &RenameTarget, range: Range) -> Range {
+    match target {
+        RenameTarget::Variable => Range {
+            start: Position::new(range.start.line, range.start.character + 1),
+            end: range.end,
+        },
+        RenameTarget::Prefix => Range {
+            start: range.start,
+            end: Position::new(range.end.line, range.end.character - 1),
+        },
+    }
+}
+
+fn prepare_rename(
+    server: &Server,
+    document_uri: &String,
+    position: &Position,
+) -> Result<Option<Range>, ResponseError> {
+    let Some(target) = rename_target(server, document_uri, position)? else {
+        return Ok(None);
+    };
+    Ok(get_occurrences(&server.state, document_uri, position)?
+        .into_iter()
+        .find(|occurrence| occurrence.range.start <= *position && *position <= occurrence.range.end)
+        .map(|occurrence| name_range(&target, occurrence.range)))
+}
+
+fn rename(
+    server: &Server,
+    document_uri: &String,
+    position: &Position,
+    new_name: &str,
+) -> Result<Vec<TextEdit>, ResponseError> {
+    let target = rename_target(server, document_uri, position)?.ok_or(ResponseError::new(
+        ErrorCode::RequestFailed,
+        "Only variables and prefixes can be renamed",
+    ))?;
+    let new_name = match target {
+        RenameTarget::Variable => {
+            let name = new_name.strip_prefix(['?', '$']).unwrap_or(new_name);
+            if !is_valid_variable_name(name) {
+                return Err(ResponseError::new(
+                    ErrorCode::InvalidParams,
+                    &format!("\"{}\" is not a valid variable name", new_name),
+                ));
+            }
+            if variable_name_is_taken(&server.state, document_uri, position, name)? {
+                return Err(ResponseError::new(
+                    ErrorCode::RequestFailed,
+                    &format!("The variable \"?{}\" is already used in this scope", name),
+                ));
+            }
+            name
+        }
+        RenameTarget::Prefix => {
+            let name = new_name.strip_suffix(':').unwrap_or(new_name);
+            if !is_valid_prefix(name) {
+                return Err(ResponseError::new(
+                    ErrorCode::InvalidParams,
+                    &format!("\"{}\" is not a valid prefix", new_name),
+                ));
+            }
+            if get_declared_prefixes(&server.state, document_uri)?
+                .iter()
+                .any(|(prefix, _range)| prefix == name)
+            {
+                return Err(ResponseError::new(
+                    ErrorCode::RequestFailed,
+                    &format!("The prefix \"{}\" is already declared", name),
+                ));
+            }
+            name
+        }
+    };
+    Ok(get_occurrences(&server.state, document_uri, position)?
+        .into_iter()
+        .map(|occurrence| TextEdit::new(name_range(&target, occurrence.range), new_name))
+        .collect())
+}
+
+/// PN_CHARS_U from the SPARQL grammar.
+fn is_pn_chars_u(char: char) -> bool {
+    char.is_alphabetic() || char == '_'
+}
+
+/// PN_CHARS from the SPARQL grammar.
+fn is_pn_chars(char: char) -> bool {
+    is_pn_chars_u(char)
+        || char.is_ascii_digit()
+        || char == '-'
+        || char == '\u{00B7}'
+        || ('\u{0300}'..='\u{036F}').contains(&char)
+        || ('\u{203F}'..='\u{2040}').contains(&char)
+}
+
+/// VARNAME from the SPARQL grammar.
+fn is_valid_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|char| is_pn_chars_u(char) || char.is_ascii_digit())
+        && chars.all(|char| is_pn_chars(char) && char != '-')
+}
+
+/// PN_PREFIX from the SPARQL grammar, the empty prefix is valid.
+fn is_valid_prefix(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        None => true,
+        Some(first) => {
+            first.is_alphabetic()
+                && chars.all(|char| is_pn_chars(char) || char == '.')
+                && !name.ends_with('.')
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use indoc::indoc;
+    use tree_sitter::Parser;
+    use tree_sitter_sparql::LANGUAGE;
+
+    use crate::server::{
+        lsp::textdocument::{Position, Range, TextDocumentItem, TextEdit},
+        message_handler::rename::{
+            is_valid_prefix, is_valid_variable_name, prepare_rename, rename,
+        },
+        state::ServerState,
+        Server,
+    };
+
+    fn setup_server(text: &str) -> Server {
+        let mut server = Server::new(|_message| {});
+        let mut state = ServerState::new();
+        let mut parser = Parser::new();
+        if let Err(err) = parser.set_language(&LANGUAGE.into()) {
+            log::error!("Could not initialize parser:\n{}", err)
+        }
+        let document = TextDocumentItem::new("uri", text);
+        let tree = parser.parse(&document.text, None);
+        state.add_document(document, tree);
+        server.state = state;
+        server
+    }
+
+    const QUERY: &str = indoc!(
+        "PREFIX wdt: <http://www.wikidata.org/prop/direct/>
+         SELECT ?item WHERE {
+           ?item wdt:P31 ?class .
+           { SELECT ?class WHERE { ?item wdt:P279 ?class } }
+         }"
+    );
+
+    #[test]
+    fn prepare_rename_variable() {
+        let server = setup_server(QUERY);
+        assert_eq!(
+            prepare_rename(&server, &"uri".to_string(), &Position::new(2, 3)).unwrap(),
+            Some(Range::new(2, 3, 2, 7))
+        );
+    }
+
+    #[test]
+    fn prepare_rename_prefix() {
+        let server = setup_server(QUERY);
+        assert_eq!(
+            prepare_rename(&server, &"uri".to_string(), &Position::new(0, 8)).unwrap(),
+            Some(Range::new(0, 7, 0, 10))
+        );
+    }
+
+    #[test]
+    fn prepare_rename_not_renameable() {
+        let server = setup_server(QUERY);
+        assert_eq!(
+            prepare_rename(&server, &"uri".to_string(), &Position::new(2, 13)).unwrap(),
+            None
+        );
+        assert_eq!(
+            prepare_rename(&server, &"uri".to_string(), &Position::new(1, 2)).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn rename_variable() {
+        let server = setup_server(QUERY);
+        assert_eq!(
+            rename(&server, &"uri".to_string(), &Position::new(2, 3), "?person").unwrap(),
+            vec![
+                TextEdit::new(Range::new(1, 8, 1, 12), "person"),
+                TextEdit::new(Range::new(2, 3, 2, 7), "person"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rename_projected_variable() {
+        let server = setup_server(QUERY);
+        assert_eq!(
+            rename(&server, &"uri".to_string(), &Position::new(3, 13), "type").unwrap(),
+            vec![
+                TextEdit::new(Range::new(2, 17, 2, 22), "type"),
+                TextEdit::new(Range::new(3, 12, 3, 17), "type"),
+                TextEdit::new(Range::new(3, 42, 3, 47), "type"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rename_prefix() {
+        let server = setup_server(QUERY);
+        assert_eq!(
+            rename(
+                &server,
+                &"uri".to_string(),
+                &Position::new(2, 9),
+                "wikidata:"
+            )
+            .unwrap(),
+            vec![
+                TextEdit::new(Range::new(0, 7, 0, 10), "wikidata"),
+                TextEdit::new(Range::new(2, 8, 2, 11), "wikidata"),
+                TextEdit::new(Range::new(3, 32, 3, 35), "wikidata"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rename_after_non_ascii() {
+        // NOTE: The ranges are in UTF-16 columns, "ü" is one column but two bytes.
+        let server = setup_server(indoc!(
+            "PREFIX rdfs: <http://www.w3.org/2000/01/rdf-schema#>
+             SELECT ?x WHERE { ?s rdfs:label \"Zürich\" ; ?p ?x . ?x rdfs:label \"Genève\" }"
+        ));
+        assert_eq!(
+            rename(&server, &"uri".to_string(), &Position::new(1, 51), "y").unwrap(),
+            vec![
+                TextEdit::new(Range::new(1, 8, 1, 9), "y"),
+                TextEdit::new(Range::new(1, 47, 1, 48), "y"),
+                TextEdit::new(Range::new(1, 52, 1, 53), "y"),
+            ]
+        );
+        assert_eq!(
+            rename(&server, &"uri".to_string(), &Position::new(1, 56), "label:").unwrap(),
+            vec![
+                TextEdit::new(Range::new(0, 7, 0, 11), "label"),
+                TextEdit::new(Range::new(1, 21, 1, 25), "label"),
+                TextEdit::new(Range::new(1, 54, 1, 58), "label"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rename_invalid() {
+        let server = setup_server(QUERY);
+        assert!(rename(&server, &"uri".to_string(), &Position::new(2, 3), "per son").is_err());
+        assert!(rename(&server, &"uri".to_string(), &Position::new(2, 9), "1wd").is_err());
+        assert!(rename(&server, &"uri".to_string(), &Position::new(2, 13), "foo").is_err());
+    }
+
+    #[test]
+    fn rename_prefix_to_declared_prefix() {
+        let server = setup_server(indoc!(
+            "PREFIX wdt: <http://www.wikidata.org/prop/direct/>
+             PREFIX wd: <http://www.wikidata.org/entity/>
+             SELECT * WHERE { ?s wdt:P31 wd:Q5 }"
+        ));
+        assert!(rename(&server, &"uri".to_string(), &Position::new(0, 8), "wd").is_err());
+    }
+
+    #[test]
+    fn rename_variable_to_used_variable() {
+        let server = setup_server(QUERY);
+        assert!(rename(&server, &"uri".to_string(), &Position::new(2, 3), "class").is_err());
+        assert!(rename(&server, &"uri".to_string(), &Position::new(3, 13), "$item").is_err());
+        let server = setup_server(indoc!(
+            "SELECT * WHERE {
+               ?a <http://example.org/p> ?b .
+               { SELECT ?c WHERE { ?c <http://example.org/p> ?d } }
+             }"
+        ));
+        assert!(rename(&server, &"uri".to_string(), &Position::new(1, 3), "b").is_err());
+        assert!(rename(&server, &"uri".to_string(), &Position::new(1, 3), "a").is_ok());
+        assert!(rename(&server, &"uri".to_string(), &Position::new(1, 3), "d").is_ok());
+    }
+
+    #[test]
+    fn valid_names() {
+        assert!(is_valid_variable_name("item_2"));
+        assert!(is_valid_variable_name("2item"));
+        assert!(!is_valid_variable_name(""));
+        assert!(!is_valid_variable_name("item-label"));
+        assert!(is_valid_prefix(""));
+        assert!(is_valid_prefix("schema.org"));
+        assert!(!is_valid_prefix("schema."));
+        assert!(!is_valid_prefix("_schema"));
+    }
+}
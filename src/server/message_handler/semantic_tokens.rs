@@ -0,0 +1,441 @@
+use std::collections::HashMap;
+
+use tree_sitter::Node;
+
+use crate::server::{
+    anaysis::ScopeModel,
+    lsp::{
+        errors::ResponseError, textdocument::Range, SemanticTokenModifier, SemanticTokenType,
+        SemanticTokensFullRequest, SemanticTokensRangeRequest, SemanticTokensResponse,
+    },
+    Server,
+};
+
+use super::formatting::KEYWORDS;
+
+pub fn handle_semantic_tokens_full_request(
+    server: &mut Server,
+    request: SemanticTokensFullRequest,
+) -> Result<SemanticTokensResponse, ResponseError> {
+    let (document, tree) = server.state.get_state(request.get_document_uri())?;
+    let tokens = collect_semantic_tokens(tree.root_node(), &document.text);
+    Ok(SemanticTokensResponse::new(
+        request.get_id(),
+        encode_semantic_tokens(tokens),
+    ))
+}
+
+pub fn handle_semantic_tokens_range_request(
+    server: &mut Server,
+    request: SemanticTokensRangeRequest,
+) -> Result<SemanticTokensResponse, ResponseError> {
+    let (document, tree) = server.state.get_state(request.get_document_uri())?;
+    let range = request.get_range();
+    let tokens = collect_semantic_tokens(tree.root_node(), &document.text)
+        .into_iter()
+        .filter(|token| range.start.line <= token.line && token.line <= range.end.line)
+        .collect();
+    Ok(SemanticTokensResponse::new(
+        request.get_id(),
+        encode_semantic_tokens(tokens),
+    ))
+}
+
+#[derive(Debug, PartialEq)]
+struct SemanticToken {
+    line: u32,
+    start: u32,
+    length: u32,
+    token_type: SemanticTokenType,
+    modifiers: u32,
+}
+
+/// Encodes the tokens in the relative format of the protocol:
+/// each token is `[deltaLine, deltaStart, length, tokenType, tokenModifiers]`.
+fn encode_semantic_tokens(mut tokens: Vec<SemanticToken>) -> Vec<u32> {
+    tokens.sort_by_key(|token| (token.line, token.start));
+    let mut previous_line = 0;
+    let mut previous_start = 0;
+    tokens
+        .into_iter()
+        .flat_map(|token| {
+            let delta_start = match token.line == previous_line {
+                true => token.start - previous_start,
+                false => token.start,
+            };
+            let delta_line = token.line - previous_line;
+            previous_line = token.line;
+            previous_start = token.start;
+            [
+                delta_line,
+                delta_start,
+                token.length,
+                token.token_type as u32,
+                token.modifiers,
+            ]
+        })
+        .collect()
+}
+
+struct TokenCollector<'a> {
+    text: &'a str,
+    lines: Vec<&'a str>,
+    scopes: ScopeModel<'a>,
+    /// Number of occurrences of the variable each `VAR` node (by id) belongs to.
+    variable_occurrences: HashMap<usize, usize>,
+    /// Number of uses of each namespace (e.g. `wdt:`) in prefixed names.
+    namespace_uses: HashMap<&'a str, usize>,
+    tokens: Vec<SemanticToken>,
+}
+
+fn collect_semantic_tokens(root: Node, text: &str) -> Vec<SemanticToken> {
+    let mut collector = TokenCollector {
+        text,
+        lines: text.split('\n').collect(),
+        scopes: ScopeModel::new(root, text),
+        variable_occurrences: HashMap::new(),
+        namespace_uses: HashMap::new(),
+        tokens: vec![],
+    };
+    collector.count_namespace_uses(root);
+    collector.collect(root);
+    collector.tokens
+}
+
+impl<'a> TokenCollector<'a> {
+    fn count_namespace_uses(&mut self, node: Node) {
+        if node.kind() == "PrefixedName" {
+            if let Some(namespace) = node
+                .child(0)
+                .and_then(|namespace| namespace.utf8_text(self.text.as_bytes()).ok())
+            {
+                *self.namespace_uses.entry(namespace).or_default() += 1;
+            }
+            return;
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.count_namespace_uses(child);
+        }
+    }
+
+    fn collect(&mut self, node: Node) {
+        let parent_kind = node.parent().map(|parent| parent.kind());
+        match node.kind() {
+            "IRIREF" | "PrefixedName" if parent_kind == Some("RdfLiteral") => {
+                self.push(&node, SemanticTokenType::Type, 0)
+            }
+            "IRIREF" | "PrefixedName"
+                if parent_kind == Some("FunctionCall")
+                    && node
+                        .parent()
+                        .and_then(|parent| parent.child_by_field_name("identifier"))
+                        == Some(node) =>
+            {
+                self.push(&node, SemanticTokenType::Function, 0)
+            }
+            "comment" => self.push(&node, SemanticTokenType::Comment, 0),
+            "VAR" => {
+                let mut modifiers = 0;
+                if self.scopes.is_binding(&node) {
+                    modifiers |= SemanticTokenModifier::Declaration as u32;
+                }
+                if self.is_unused_variable(node) {
+                    modifiers |= SemanticTokenModifier::Unused as u32;
+                }
+                self.push(&node, SemanticTokenType::Variable, modifiers)
+            }
+            "PNAME_NS" => {
+                let modifiers = match parent_kind {
+                    Some("PrefixDecl") => {
+                        let uses = node
+                            .utf8_text(self.text.as_bytes())
+                            .ok()
+                            .and_then(|namespace| self.namespace_uses.get(namespace))
+                            .copied()
+                            .unwrap_or(0);
+                        SemanticTokenModifier::Declaration as u32
+                            | match uses {
+                                0 => SemanticTokenModifier::Unused as u32,
+                                _ => 0,
+                            }
+                    }
+                    _ => 0,
+                };
+                self.push(&node, SemanticTokenType::Namespace, modifiers)
+            }
+            "PN_LOCAL" => self.push(&node, SemanticTokenType::LocalName, 0),
+            "IRIREF" => self.push(&node, SemanticTokenType::Iri, 0),
+            "STRING_LITERAL" | "STRING_LITERAL_LONG" => {
+                self.push(&node, SemanticTokenType::String, 0)
+            }
+            "INTEGER" | "DECIMAL" | "DOUBLE" => self.push(&node, SemanticTokenType::Number, 0),
+            "boolean_literal" => self.push(&node, SemanticTokenType::Keyword, 0),
+            "LANGTAG" => self.push(&node, SemanticTokenType::LanguageTag, 0),
+            "BLANK_NODE_LABEL" | "ANON" => self.push(&node, SemanticTokenType::BlankNode, 0),
+            kind if !node.is_named() && KEYWORDS.contains(&kind) => {
+                // NOTE: The first keyword of a build-in call is the function name.
+                let is_function = matches!(
+                    parent_kind,
+                    Some(
+                        "BuildInCall"
+                            | "Aggregate"
+                            | "RegexExpression"
+                            | "SubstringExpression"
+                            | "String_replace_expression"
+                    )
+                ) && node.prev_sibling().is_none();
+                match is_function {
+                    true => self.push(&node, SemanticTokenType::Function, 0),
+                    false => self.push(&node, SemanticTokenType::Keyword, 0),
+                }
+            }
+            _ => {
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    self.collect(child);
+                }
+            }
+        }
+    }
+
+    /// A variable is unused if it occurs only once,
+    /// unless it is projected by a `SELECT *`.
+    fn is_unused_variable(&mut self, variable: Node) -> bool {
+        let occurrences = match self.variable_occurrences.get(&variable.id()) {
+            Some(occurrences) => *occurrences,
+            None => {
+                let occurrences = self.scopes.occurrences(&variable);
+                for occurrence in occurrences.iter() {
+                    self.variable_occurrences
+                        .insert(occurrence.id(), occurrences.len());
+                }
+                occurrences.len()
+            }
+        };
+        occurrences == 1 && !is_projected_by_wildcard(variable)
+    }
+
+    /// Converts a byte column of `line` into a UTF-16 column, as the protocol expects.
+    fn utf16_column(&self, line: u32, column: u32) -> u32 {
+        self.lines
+            .get(line as usize)
+            .and_then(|line| line.get(..column as usize))
+            .map(|prefix| prefix.encode_utf16().count() as u32)
+            .unwrap_or(column)
+    }
+
+    /// Adds a token for `node`. Tokens spanning multiple lines are split into one token per line.
+    fn push(&mut self, node: &Node, token_type: SemanticTokenType, modifiers: u32) {
+        let range = Range::from_node(node);
+        for line in range.start.line..=range.end.line {
+            let start = match line == range.start.line {
+                true => self.utf16_column(line, range.start.character),
+                false => 0,
+            };
+            let end = match line == range.end.line {
+                true => self.utf16_column(line, range.end.character),
+                false => self
+                    .lines
+                    .get(line as usize)
+                    .map(|line| line.encode_utf16().count() as u32)
+                    .unwrap_or(start),
+            };
+            if end > start {
+                self.tokens.push(SemanticToken {
+                    line,
+                    start,
+                    length: end - start,
+                    token_type,
+                    modifiers,
+                });
+            }
+        }
+    }
+}
+
+fn is_projected_by_wildcard(variable: Node) -> bool {
+    let mut ancestor = variable.parent();
+    while let Some(node) = ancestor {
+        if matches!(node.kind(), "SelectQuery" | "SubSelect") {
+            let mut cursor = node.walk();
+            let has_wildcard = node
+                .children(&mut cursor)
+                .find(|child| child.kind() == "SelectClause")
+                .is_some_and(|select_clause| {
+                    let mut cursor = select_clause.walk();
+                    let has_wildcard = select_clause
+                        .children(&mut cursor)
+                        .any(|child| child.kind() == "*");
+                    has_wildcard
+                });
+            return has_wildcard;
+        }
+        ancestor = node.parent();
+    }
+    false
+}
+
+#[cfg(test)]
+mod test {
+    use indoc::indoc;
+    use tree_sitter::Parser;
+    use tree_sitter_sparql::LANGUAGE;
+
+    use crate::server::lsp::SemanticTokenType;
+
+    use super::{collect_semantic_tokens, encode_semantic_tokens, SemanticToken};
+
+    fn tokens(text: &str) -> Vec<(u32, u32, u32, SemanticTokenType, u32)> {
+        let mut parser = Parser::new();
+        parser.set_language(&LANGUAGE.into()).unwrap();
+        let tree = parser.parse(text, None).unwrap();
+        collect_semantic_tokens(tree.root_node(), text)
+            .into_iter()
+            .map(|token| {
+                (
+                    token.line,
+                    token.start,
+                    token.length,
+                    token.token_type,
+                    token.modifiers,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn semantic_tokens_query() {
+        use SemanticTokenType::*;
+        assert_eq!(
+            tokens(indoc!(
+                "PREFIX wdt: <http://www.wikidata.org/prop/direct/>
+                 PREFIX xsd: <http://www.w3.org/2001/XMLSchema#>
+                 SELECT ?s WHERE {
+                   ?s wdt:P31 _:b, \"x\"@en ; a 1 .
+                   FILTER (STRLEN(?s) > 2) # done
+                 }"
+            )),
+            vec![
+                (0, 0, 6, Keyword, 0),
+                (0, 7, 4, Namespace, 0b01),
+                (0, 12, 38, Iri, 0),
+                (1, 0, 6, Keyword, 0),
+                (1, 7, 4, Namespace, 0b11),
+                (1, 12, 35, Iri, 0),
+                (2, 0, 6, Keyword, 0),
+                (2, 7, 2, Variable, 0),
+                (2, 10, 5, Keyword, 0),
+                (3, 2, 2, Variable, 0b01),
+                (3, 5, 4, Namespace, 0),
+                (3, 9, 3, LocalName, 0),
+                (3, 13, 3, BlankNode, 0),
+                (3, 18, 3, String, 0),
+                (3, 21, 3, LanguageTag, 0),
+                (3, 27, 1, Keyword, 0),
+                (3, 29, 1, Number, 0),
+                (4, 2, 6, Keyword, 0),
+                (4, 10, 6, Function, 0),
+                (4, 17, 2, Variable, 0),
+                (4, 23, 1, Number, 0),
+                (4, 26, 6, Comment, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn semantic_tokens_unused_variable() {
+        use SemanticTokenType::*;
+        assert_eq!(
+            tokens("SELECT ?a { ?a ?b ?c . ?c ?d ?a }")
+                .into_iter()
+                .filter(|token| token.3 == Variable)
+                .collect::<Vec<_>>(),
+            vec![
+                (0, 7, 2, Variable, 0b00),
+                (0, 12, 2, Variable, 0b01),
+                (0, 15, 2, Variable, 0b11),
+                (0, 18, 2, Variable, 0b01),
+                (0, 23, 2, Variable, 0b01),
+                (0, 26, 2, Variable, 0b11),
+                (0, 29, 2, Variable, 0b01),
+            ]
+        );
+        assert_eq!(
+            tokens("SELECT * { ?a ?b ?c }")
+                .into_iter()
+                .filter(|token| token.3 == Variable)
+                .map(|token| token.4)
+                .collect::<Vec<_>>(),
+            vec![0b01, 0b01, 0b01]
+        );
+    }
+
+    #[test]
+    fn semantic_tokens_datatype_and_function() {
+        use SemanticTokenType::*;
+        assert_eq!(
+            tokens("SELECT * { BIND (<f>(\"1\"^^xsd:int) AS ?x) }"),
+            vec![
+                (0, 0, 6, Keyword, 0),
+                (0, 11, 4, Keyword, 0),
+                (0, 17, 3, Function, 0),
+                (0, 21, 3, String, 0),
+                (0, 26, 7, Type, 0),
+                (0, 35, 2, Keyword, 0),
+                (0, 38, 2, Variable, 0b01),
+            ]
+        );
+    }
+
+    #[test]
+    fn semantic_tokens_non_ascii() {
+        use SemanticTokenType::*;
+        assert_eq!(
+            tokens("SELECT * { ?s <http://ex.org/名> \"Zürich\"@de , \"😀\"@en }"),
+            vec![
+                (0, 0, 6, Keyword, 0),
+                (0, 11, 2, Variable, 0b01),
+                (0, 14, 17, Iri, 0),
+                (0, 32, 8, String, 0),
+                (0, 40, 3, LanguageTag, 0),
+                (0, 46, 4, String, 0),
+                (0, 50, 3, LanguageTag, 0),
+            ]
+        );
+        assert_eq!(
+            tokens("SELECT * { ?s ?p \"\"\"ä\nö\"\"\" }")
+                .into_iter()
+                .filter(|token| token.3 == String)
+                .collect::<Vec<_>>(),
+            vec![(0, 17, 4, String, 0), (1, 0, 4, String, 0)]
+        );
+    }
+
+    #[test]
+    fn semantic_tokens_multi_line_string() {
+        use SemanticTokenType::*;
+        assert_eq!(
+            tokens("SELECT * { ?s ?p \"\"\"a\nbc\"\"\" }")
+                .into_iter()
+                .filter(|token| token.3 == String)
+                .collect::<Vec<_>>(),
+            vec![(0, 17, 4, String, 0), (1, 0, 5, String, 0)]
+        );
+    }
+
+    #[test]
+    fn encode() {
+        let token = |line, start, length| SemanticToken {
+            line,
+            start,
+            length,
+            token_type: SemanticTokenType::Variable,
+            modifiers: 0,
+        };
+        assert_eq!(
+            encode_semantic_tokens(vec![token(2, 4, 2), token(0, 3, 1), token(0, 7, 3)]),
+            vec![0, 3, 1, 1, 0, 0, 4, 3, 1, 0, 2, 4, 2, 1, 0]
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use tree_sitter::Node;
+
+use crate::server::{
+    lsp::{
+        errors::{ErrorCode, ResponseError},
+        ParameterInformation, SignatureHelp, SignatureHelpRequest, SignatureHelpResponse,
+        SignatureInformation,
+    },
+    Server,
+};
+
+use super::builtins::{find_builtin_function, BuiltinFunction, ParameterKind};
+
+pub fn handle_signature_help_request(
+    server: &mut Server,
+    request: SignatureHelpRequest,
+) -> Result<SignatureHelpResponse, ResponseError> {
+    let (document, tree) = server.state.get_state(request.get_document_uri())?;
+    let cursor = request
+        .get_position()
+        .to_byte_index(&document.text)
+        .ok_or(ResponseError::new(
+            ErrorCode::InvalidParams,
+            &format!(
+                "Position {} is not inside the document",
+                request.get_position()
+            ),
+        ))?;
+    let mut leaves = vec![];
+    collect_leaves_before(tree.root_node(), cursor, &mut leaves);
+    Ok(SignatureHelpResponse::new(
+        request.get_id(),
+        find_call(&leaves).map(|(function, argument)| signature_help(function, argument)),
+    ))
+}
+
+/// Collects the tokens that end before the cursor, in document order.
+///
+/// NOTE: The call is usually incomplete while its arguments are typed, the tokens are
+/// available even if the parser could not match them to a rule.
+fn collect_leaves_before<'a>(node: Node<'a>, cursor: usize, leaves: &mut Vec<Node<'a>>) {
+    if node.start_byte() >= cursor {
+        return;
+    }
+    if node.child_count() == 0 {
+        if node.end_byte() <= cursor && !node.is_missing() {
+            leaves.push(node);
+        }
+        return;
+    }
+    let mut tree_cursor = node.walk();
+    let children: Vec<Node> = node.children(&mut tree_cursor).collect();
+    for child in children {
+        collect_leaves_before(child, cursor, leaves);
+    }
+}
+
+/// Finds the innermost built-in call around the cursor and the index of the argument
+/// the cursor is in.
+///
+/// Walks back from the cursor to the unmatched `(`. Brackets that do not belong to a built-in
+/// call (e.g. of `FILTER` or a nested expression) are skipped.
+/// The search ends at the boundary of the enclosing group graph pattern.
+fn find_call(leaves: &[Node]) -> Option<(&'static BuiltinFunction, usize)> {
+    let mut parentheses = 0;
+    let mut braces = 0;
+    let mut argument = 0;
+    for (index, leaf) in leaves.iter().enumerate().rev() {
+        match leaf.kind() {
+            "}" => braces += 1,
+            "{" if braces > 0 => braces -= 1,
+            "{" => return None,
+            _ if braces > 0 => {}
+            ")" => parentheses += 1,
+            "(" if parentheses > 0 => parentheses -= 1,
+            "(" => {
+                let function = index
+                    .checked_sub(1)
+                    .map(|index| leaves[index])
+                    .filter(|callee| !callee.is_named())
+                    .and_then(|callee| find_builtin_function(callee.kind()));
+                match function {
+                    Some(function) => return Some((function, argument)),
+                    None => argument = 0,
+                }
+            }
+            "," if parentheses == 0 => argument += 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+fn signature_help(function: &BuiltinFunction, argument: usize) -> SignatureHelp {
+    let mut offset = function.name.len() + 1;
+    let parameters: Vec<ParameterInformation> = function
+        .parameter_labels()
+        .iter()
+        .zip(function.parameters)
+        .map(|(label, parameter)| {
+            let start = offset;
+            offset += label.len() + 2;
+            ParameterInformation {
+                label: (start as u32, (start + label.len()) as u32),
+                documentation: parameter
+                    .description
+                    .map(|description| description.to_string()),
+            }
+        })
+        .collect();
+    // NOTE: All further arguments belong to a variadic parameter.
+    let active_parameter = match function.parameters.last() {
+        Some(last) if matches!(last.kind, ParameterKind::Variadic) => {
+            argument.min(function.parameters.len() - 1)
+        }
+        _ => argument,
+    };
+    SignatureHelp {
+        signatures: vec![SignatureInformation {
+            label: function.signature(),
+            documentation: Some(function.description.to_string()),
+            parameters,
+        }],
+        active_signature: 0,
+        active_parameter: active_parameter as u32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tree_sitter::Parser;
+
+    use crate::server::{
+        lsp::{textdocument::TextDocumentItem, SignatureHelpRequest},
+        state::ServerState,
+        Server,
+    };
+
+    use super::handle_signature_help_request;
+
+    /// The signature help at the `|` in `text`, as (label, active parameter label).
+    fn signature_help(text: &str) -> Option<(String, String)> {
+        let cursor = text.find('|').unwrap();
+        let line = text[..cursor].matches('\n').count() as u32;
+        let character = (cursor - text[..cursor].rfind('\n').map_or(0, |index| index + 1)) as u32;
+        let text = text.replace('|', "");
+        let mut server = Server::new(|_message| {});
+        let mut state = ServerState::new();
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_sparql::LANGUAGE.into())
+            .unwrap();
+        let tree = parser.parse(&text, None);
+        state.add_document(TextDocumentItem::new("uri", &text), tree);
+        server.state = state;
+        let request: SignatureHelpRequest = serde_json::from_value(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "textDocument/signatureHelp",
+            "params": {
+                "textDocument": {"uri": "uri"},
+                "position": {"line": line, "character": character}
+            }
+        }))
+        .unwrap();
+        let response = handle_signature_help_request(&mut server, request).unwrap();
+        let value = serde_json::to_value(response).unwrap();
+        let signature = value["result"]["signatures"].get(0)?;
+        let label = signature["label"].as_str().unwrap().to_string();
+        let active = value["result"]["activeParameter"].as_u64().unwrap() as usize;
+        let parameter = match signature["parameters"].get(active) {
+            Some(parameter) => {
+                let (start, end) = (
+                    parameter["label"][0].as_u64().unwrap() as usize,
+                    parameter["label"][1].as_u64().unwrap() as usize,
+                );
+                label[start..end].to_string()
+            }
+            None => String::new(),
+        };
+        Some((label, parameter))
+    }
+
+    #[test]
+    fn signature_help_active_parameter() {
+        assert_eq!(
+            signature_help("SELECT * WHERE { FILTER (REGEX(|"),
+            Some((
+                "REGEX(text, pattern, [flags]) -> xsd:boolean".to_string(),
+                "text".to_string()
+            ))
+        );
+        assert_eq!(
+            signature_help("SELECT * WHERE { FILTER (REGEX(?x, \"a,b\", |) }"),
+            Some((
+                "REGEX(text, pattern, [flags]) -> xsd:boolean".to_string(),
+                "[flags]".to_string()
+            ))
+        );
+        assert_eq!(
+            signature_help("SELECT * WHERE { BIND (SUBSTR(STR(?x), |"),
+            Some((
+                "SUBSTR(source, start, [length]) -> string".to_string(),
+                "start".to_string()
+            ))
+        );
+        assert_eq!(
+            signature_help("SELECT * WHERE { BIND (SUBSTR(STR(?x|), 1) AS ?y) }"),
+            Some((
+                "STR(term) -> simple literal".to_string(),
+                "term".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn signature_help_nested_expressions() {
+        assert_eq!(
+            signature_help("SELECT (COUNT(DISTINCT ?x|) AS ?c) WHERE {}"),
+            Some((
+                "COUNT(expression) -> xsd:integer".to_string(),
+                "expression".to_string()
+            ))
+        );
+        assert_eq!(
+            signature_help("SELECT * WHERE { FILTER (CONCAT(?a, (?b + ?c), ?d, |) }"),
+            Some((
+                "CONCAT(string...) -> string".to_string(),
+                "string...".to_string()
+            ))
+        );
+        assert_eq!(
+            signature_help("SELECT * WHERE { FILTER (IF(EXISTS { ?s ?p ?a, ?b }, |"),
+            Some((
+                "IF(condition, then, else) -> term".to_string(),
+                "then".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn signature_help_outside_of_calls() {
+        assert_eq!(signature_help("SELECT * WHERE { FILTER (?x = |) }"), None);
+        assert_eq!(signature_help("SELECT * WHERE { ?s ?p ?a, |"), None);
+        assert_eq!(
+            signature_help("SELECT * WHERE { FILTER (REGEX(?x, \"a\")) ?s ?p |"),
+            None
+        );
+        assert_eq!(signature_help("SELECT * WHERE { BIND (ex:f(?x, |"), None);
+    }
+}
@@ -1,14 +1,18 @@
-use log::{error, info, warn};
+use log::{error, info};
 
 use crate::server::{
+    configuration::DiagnosticsMode,
     lsp::{
         errors::{ErrorCode, ResponseError},
-        DidChangeTextDocumentNotification, DidOpenTextDocumentNotification,
-        DidSaveTextDocumentNotification,
+        DidChangeTextDocumentNotification, DidCloseTextDocumentNotification,
+        DidOpenTextDocumentNotification, DidSaveTextDocumentNotification,
     },
+    state::now,
     Server,
 };
 
+use super::{clear_diagnostics, publish_diagnostics};
+
 pub(super) fn handle_did_open_notification(
     server: &mut Server,
     did_open_notification: DidOpenTextDocumentNotification,
@@ -18,8 +22,12 @@ pub(super) fn handle_did_open_notification(
         did_open_notification.params.text_document.uri
     );
     let document = did_open_notification.get_text_document();
+    let uri = document.uri.clone();
     let tree = server.tools.parser.parse(document.text.as_bytes(), None);
     server.state.add_document(document, tree);
+    if server.settings.diagnostics.mode == DiagnosticsMode::Push {
+        publish_diagnostics(server, &uri);
+    }
     Ok(())
 }
 
@@ -27,30 +35,51 @@ pub(super) fn handle_did_change_notification(
     server: &mut Server,
     did_change_notification: DidChangeTextDocumentNotification,
 ) -> Result<(), ResponseError> {
-    let uri = &did_change_notification.params.text_document.base.uri;
-    if let Some(document) = server
-        .state
-        .change_document(uri, did_change_notification.params.content_changes)
-    {
-        let bytes = document.text.as_bytes();
-        // let old_tree = server.state.get_tree(&uri).ok();
-        let new_tree = server.tools.parser.parse(bytes, None);
-        if new_tree.is_none() {
-            warn!("Could not build new parse-tree for \"{}\"", uri);
+    let text_document = &did_change_notification.params.text_document;
+    match server.state.change_document(
+        &text_document.base.uri,
+        text_document.version(),
+        did_change_notification.params.content_changes,
+        &mut server.tools.parser,
+    ) {
+        Ok(_document) => {
+            // NOTE: The diagnostics get published once the changes settle down.
+            if server.settings.diagnostics.mode == DiagnosticsMode::Push {
+                let due = now() + server.settings.diagnostics.debounce_ms;
+                server
+                    .state
+                    .mark_diagnostics_pending(&text_document.base.uri, due);
+            }
+            Ok(())
         }
-        if let Err(err) = server.state.update_tree(uri, new_tree) {
-            error!("{}", err.message);
-            return Err(ResponseError::new(
-                ErrorCode::InternalError,
-                &format!("Error while building parse-tree:\n{}", err.message),
-            ));
+        Err(error) => {
+            error!("Did-Change notification failed: {}", error.message);
+            Err(error)
         }
+    }
+}
 
-        Ok(())
-    } else {
-        let message = format!("Did-Change request failed, document not found: \"{}\"", uri);
-        error!("{}", message);
-        Err(ResponseError::new(ErrorCode::InvalidRequest, &message))
+pub(super) fn handle_did_close_notification(
+    server: &mut Server,
+    did_close_notification: DidCloseTextDocumentNotification,
+) -> Result<(), ResponseError> {
+    let uri = &did_close_notification.params.text_document.uri;
+    info!("closed text document: \"{}\"", uri);
+    match server.state.remove_document(uri) {
+        Some(_document) => {
+            if server.settings.diagnostics.mode == DiagnosticsMode::Push {
+                clear_diagnostics(server, uri);
+            }
+            Ok(())
+        }
+        None => {
+            let message = format!(
+                "Did-Close notification failed, document not found: \"{}\"",
+                uri
+            );
+            error!("{}", message);
+            Err(ResponseError::new(ErrorCode::InvalidRequest, &message))
+        }
     }
 }
 
@@ -64,3 +93,152 @@ pub(super) fn handle_did_save_notification(
     );
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc, thread::sleep};
+
+    use serde_json::Value;
+
+    use crate::server::{configuration::DiagnosticsMode, state::ServerStatus, Server};
+
+    fn setup_server(mode: DiagnosticsMode) -> (Server, Rc<RefCell<Vec<Value>>>) {
+        let sent_messages = Rc::new(RefCell::new(vec![]));
+        let sink = sent_messages.clone();
+        let mut server = Server::new(move |message| {
+            sink.borrow_mut()
+                .push(serde_json::from_str(&message).unwrap())
+        });
+        server.state.status = ServerStatus::Running;
+        server.settings.diagnostics.mode = mode;
+        server.settings.diagnostics.debounce_ms = 50;
+        (server, sent_messages)
+    }
+
+    fn did_open(text: &str) -> String {
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": {
+                "textDocument": {"uri": "query.rq", "languageId": "sparql", "version": 1, "text": text}
+            }
+        })
+        .to_string()
+    }
+
+    fn did_change(version: u32, text: &str) -> String {
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didChange",
+            "params": {
+                "textDocument": {"uri": "query.rq", "version": version},
+                "contentChanges": [{
+                    "range": {"start": {"line": 0, "character": 0}, "end": {"line": 1, "character": 0}},
+                    "text": text
+                }]
+            }
+        })
+        .to_string()
+    }
+
+    fn hover() -> String {
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "textDocument/hover",
+            "params": {
+                "textDocument": {"uri": "query.rq"},
+                "position": {"line": 0, "character": 0}
+            }
+        })
+        .to_string()
+    }
+
+    fn did_close() -> String {
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didClose",
+            "params": {"textDocument": {"uri": "query.rq"}}
+        })
+        .to_string()
+    }
+
+    fn published(messages: &Rc<RefCell<Vec<Value>>>) -> Vec<(Value, usize)> {
+        messages
+            .borrow_mut()
+            .drain(..)
+            .filter(|message| message["method"] == "textDocument/publishDiagnostics")
+            .map(|message| {
+                (
+                    message["params"]["version"].clone(),
+                    message["params"]["diagnostics"].as_array().unwrap().len(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn push_diagnostics_on_open() {
+        let (mut server, messages) = setup_server(DiagnosticsMode::Push);
+        server.handle_message(did_open("PREFIX ab: <ab>\nSELECT * {}\n"));
+        assert_eq!(published(&messages), vec![(Value::from(1), 1)]);
+    }
+
+    #[test]
+    fn push_diagnostics_after_changes_settled() {
+        let (mut server, messages) = setup_server(DiagnosticsMode::Push);
+        server.handle_message(did_open("SELECT * {}\n"));
+        assert_eq!(published(&messages), vec![(Value::from(1), 0)]);
+        server.handle_message(did_change(2, "PREFIX ab: <ab>\nSELECT * {}\n"));
+        server.handle_message(did_change(3, "PREFIX ab: <ab>\n"));
+        server.handle_idle();
+        assert_eq!(published(&messages), vec![]);
+        sleep(server.diagnostics_due_in().unwrap());
+        server.handle_idle();
+        assert_eq!(published(&messages), vec![(Value::from(3), 1)]);
+        assert_eq!(server.diagnostics_due_in(), None);
+        server.handle_idle();
+        assert_eq!(published(&messages), vec![]);
+    }
+
+    #[test]
+    fn push_diagnostics_once_after_burst() {
+        let (mut server, messages) = setup_server(DiagnosticsMode::Push);
+        server.handle_message(did_open("\nSELECT * {}\n"));
+        assert_eq!(published(&messages), vec![(Value::from(1), 0)]);
+        for version in 2..12 {
+            server.handle_message(did_change(version, "PREFIX ab: <ab>\n"));
+            server.handle_message(hover());
+            server.handle_idle();
+        }
+        assert_eq!(published(&messages), vec![]);
+        sleep(server.diagnostics_due_in().unwrap());
+        server.handle_idle();
+        server.handle_idle();
+        assert_eq!(published(&messages), vec![(Value::from(11), 1)]);
+    }
+
+    #[test]
+    fn push_diagnostics_before_next_message() {
+        let (mut server, messages) = setup_server(DiagnosticsMode::Push);
+        server.handle_message(did_open("SELECT * {}\n"));
+        server.handle_message(did_change(2, "PREFIX ab: <ab>\nSELECT * {}\n"));
+        assert_eq!(published(&messages), vec![(Value::from(1), 0)]);
+        sleep(server.diagnostics_due_in().unwrap());
+        server.handle_message(did_close());
+        assert_eq!(
+            published(&messages),
+            vec![(Value::from(2), 1), (Value::Null, 0)]
+        );
+    }
+
+    #[test]
+    fn pull_mode_publishes_nothing() {
+        let (mut server, messages) = setup_server(DiagnosticsMode::Pull);
+        server.handle_message(did_open("PREFIX ab: <ab>\nSELECT * {}\n"));
+        server.handle_message(did_change(2, "PREFIX ab: <ab>\nSELECT * {}\n"));
+        server.handle_idle();
+        server.handle_message(did_close());
+        assert_eq!(published(&messages), vec![]);
+    }
+}
@@ -3,11 +3,15 @@ mod capabilities;
 mod common;
 mod configuration;
 mod lsp;
+mod prefixes;
 mod state;
 mod tools;
+mod vocabulary;
 
 mod message_handler;
 
+use std::time::Duration;
+
 use capabilities::create_capabilities;
 use configuration::Settings;
 use log::{error, info};
@@ -15,14 +19,14 @@ use lsp::{
     rpc::{RecoverId, RequestIdOrNull, ResponseMessage},
     ServerInfo,
 };
-use message_handler::dispatch;
+use message_handler::{dispatch, publish_due_diagnostics};
 
 // WARNING: This is a temporary soloution to export the format function directly
 // will remove soon (12.12.24)
 #[allow(unused_imports)]
 pub use message_handler::format_raw;
 
-use state::ServerState;
+use state::{now, ServerState};
 use tools::Tools;
 use wasm_bindgen::prelude::wasm_bindgen;
 
@@ -40,15 +44,16 @@ impl Server {
     pub fn new(write_function: impl Fn(String) -> () + 'static) -> Server {
         let version = env!("CARGO_PKG_VERSION");
         info!("Started Language Server: Qlue-ls - version: {}", version);
+        let settings = Settings::new();
         Self {
             state: ServerState::new(),
-            settings: Settings::new(),
-            capabilities: create_capabilities(),
+            capabilities: create_capabilities(&settings),
+            tools: Tools::initiate(&settings),
+            settings,
             server_info: ServerInfo {
                 name: "Qlue-ls".to_string(),
                 version: Some(version.to_string()),
             },
-            tools: Tools::initiate(),
             send_message_clusure: Box::new(write_function),
         }
     }
@@ -88,6 +93,21 @@ impl Server {
         }
     }
 
+    /// Signals that there are currently no further messages to handle.
+    ///
+    /// Publishes the diagnostics that were held back while changes came in and are due by now.
+    pub fn handle_idle(&mut self) {
+        publish_due_diagnostics(self);
+    }
+
+    /// The time until the next held back diagnostics are due,
+    /// the host should call `handle_idle` after it, unless a message comes in first.
+    pub fn diagnostics_due_in(&self) -> Option<Duration> {
+        self.state
+            .next_diagnostics_due()
+            .map(|due| Duration::from_millis(due.saturating_sub(now())))
+    }
+
     fn send_message(&self, message: String) {
         (self.send_message_clusure)(message);
     }
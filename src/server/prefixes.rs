@@ -0,0 +1,343 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use curies::{Converter, Record};
+use log::{error, info};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::configuration::{BuiltinPrefixesSettings, Settings};
+
+/// Snapshot of common vocabulary prefixes, bundled into the binary.
+const SNAPSHOT: &str = include_str!("prefixes/snapshot.json");
+
+#[derive(Deserialize)]
+struct Snapshot {
+    version: String,
+    prefixes: Value,
+}
+
+/// The prefixes of the bundled snapshot, without the disabled ones.
+fn builtin_prefixes(settings: &BuiltinPrefixesSettings) -> Vec<(String, String)> {
+    if !settings.enabled {
+        return vec![];
+    }
+    let snapshot: Snapshot = match serde_json::from_str(SNAPSHOT) {
+        Ok(snapshot) => snapshot,
+        Err(error) => {
+            error!("Could not read bundled prefix snapshot:\n{}", error);
+            return vec![];
+        }
+    };
+    match parse_prefix_map(&snapshot.prefixes) {
+        Ok(prefixes) => {
+            info!("Loaded bundled prefix snapshot {}", snapshot.version);
+            prefixes
+                .into_iter()
+                .filter(|(prefix, _namespace)| !settings.disabled.contains(prefix))
+                .collect()
+        }
+        Err(message) => {
+            error!("Could not read bundled prefix snapshot:\n{}", message);
+            vec![]
+        }
+    }
+}
+
+/// Builds the prefix registry.
+///
+/// The sources are applied in this order, later sources override earlier ones:
+/// 1. the bundled snapshot (see `builtin_prefixes`)
+/// 2. the files listed in `prefix_files` (in the given order)
+/// 3. the inline `[[prefixes]]` declarations
+pub(super) fn build_prefix_registry(settings: &Settings) -> Converter {
+    let mut prefixes = builtin_prefixes(&settings.builtin_prefixes);
+    for path in settings.prefix_files.iter() {
+        match load_prefix_file(Path::new(path)) {
+            Ok(file_prefixes) => {
+                info!("Loaded {} prefixes from \"{}\"", file_prefixes.len(), path);
+                prefixes.extend(file_prefixes);
+            }
+            Err(message) => error!("Could not load prefixes from \"{}\":\n{}", path, message),
+        }
+    }
+    prefixes.extend(
+        settings
+            .prefixes
+            .iter()
+            .map(|setting| (setting.prefix.clone(), setting.namespace.clone())),
+    );
+    create_converter(prefixes)
+}
+
+/// Creates a converter from (prefix, namespace) pairs.
+/// If a prefix or namespace occurs multiple times, the last occurrence wins.
+fn create_converter(prefixes: Vec<(String, String)>) -> Converter {
+    let mut namespaces: HashMap<String, String> = HashMap::new();
+    let mut prefixes_by_namespace: HashMap<String, String> = HashMap::new();
+    let mut order: Vec<String> = vec![];
+    for (prefix, namespace) in prefixes {
+        if let Some(old_namespace) = namespaces.insert(prefix.clone(), namespace.clone()) {
+            prefixes_by_namespace.remove(&old_namespace);
+        }
+        if let Some(old_prefix) = prefixes_by_namespace.insert(namespace, prefix.clone()) {
+            if old_prefix != prefix {
+                namespaces.remove(&old_prefix);
+            }
+        }
+        order.retain(|other| other != &prefix);
+        order.push(prefix);
+    }
+    let mut converter = Converter::new(":");
+    for prefix in order {
+        if let Some(namespace) = namespaces.get(&prefix) {
+            if let Err(error) = converter.add_record(Record::new(&prefix, namespace)) {
+                error!("Could not add prefix \"{}\":\n{}", prefix, error);
+            }
+        }
+    }
+    converter
+}
+
+/// Loads prefixes from a file.
+///
+/// `.json` and `.jsonld` files are either prefix.cc-style prefix maps
+/// (`{"foaf": "http://xmlns.com/foaf/0.1/"}`) or JSON-LD documents with a `@context`.
+/// Any other file is scanned for Turtle `@prefix` (or SPARQL `PREFIX`) declarations.
+fn load_prefix_file(path: &Path) -> Result<Vec<(String, String)>, String> {
+    let content = fs::read_to_string(path).map_err(|error| error.to_string())?;
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") | Some("jsonld") => {
+            let value: Value = serde_json::from_str(&content).map_err(|error| error.to_string())?;
+            match value.get("@context") {
+                Some(context) => Ok(parse_jsonld_context(context)),
+                None => parse_prefix_map(&value),
+            }
+        }
+        _ => Ok(parse_turtle_prefixes(&content)),
+    }
+}
+
+/// Parses a prefix.cc-style prefix map: a flat JSON object from prefix to namespace.
+fn parse_prefix_map(value: &Value) -> Result<Vec<(String, String)>, String> {
+    let object = value
+        .as_object()
+        .ok_or("A prefix map has to be a JSON object".to_string())?;
+    let mut prefixes: Vec<(String, String)> = object
+        .iter()
+        .filter_map(|(prefix, namespace)| Some((prefix.clone(), namespace.as_str()?.to_string())))
+        .collect();
+    prefixes.sort();
+    Ok(prefixes)
+}
+
+/// Collects the prefixes of a JSON-LD `@context`.
+///
+/// A term is a prefix if its IRI ends with `/` or `#`, or if it is marked with `"@prefix": true`.
+/// Remote contexts (strings) are skipped.
+fn parse_jsonld_context(context: &Value) -> Vec<(String, String)> {
+    match context {
+        Value::Array(contexts) => contexts.iter().flat_map(parse_jsonld_context).collect(),
+        Value::Object(terms) => {
+            let mut prefixes: Vec<(String, String)> = terms
+                .iter()
+                .filter(|(term, _)| !term.starts_with('@'))
+                .filter_map(|(term, definition)| match definition {
+                    Value::String(iri) if iri.ends_with('/') || iri.ends_with('#') => {
+                        Some((term.clone(), iri.clone()))
+                    }
+                    Value::Object(definition)
+                        if definition.get("@prefix") == Some(&Value::Bool(true)) =>
+                    {
+                        Some((term.clone(), definition.get("@id")?.as_str()?.to_string()))
+                    }
+                    _ => None,
+                })
+                .collect();
+            prefixes.sort();
+            prefixes
+        }
+        _ => vec![],
+    }
+}
+
+/// Collects the prefix declarations of a Turtle document, line by line.
+fn parse_turtle_prefixes(text: &str) -> Vec<(String, String)> {
+    text.lines().filter_map(parse_prefix_line).collect()
+}
+
+/// Parses `@prefix ex: <http://example.org/> .` or `PREFIX ex: <http://example.org/>`.
+fn parse_prefix_line(line: &str) -> Option<(String, String)> {
+    let line = line.trim_start();
+    let rest = match line.strip_prefix("@prefix") {
+        Some(rest) => rest,
+        None if line.get(..6)?.eq_ignore_ascii_case("prefix") => &line[6..],
+        None => return None,
+    };
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let (prefix, rest) = rest.trim_start().split_once(':')?;
+    if prefix.contains(char::is_whitespace) {
+        return None;
+    }
+    let rest = rest.trim_start().strip_prefix('<')?;
+    let (namespace, _rest) = rest.split_once('>')?;
+    Some((prefix.to_string(), namespace.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::server::configuration::{BuiltinPrefixesSettings, PrefixSetting, Settings};
+
+    use super::{
+        build_prefix_registry, builtin_prefixes, create_converter, parse_jsonld_context,
+        parse_prefix_map, parse_turtle_prefixes,
+    };
+
+    fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(prefix, namespace)| (prefix.to_string(), namespace.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn turtle_prefixes() {
+        let text = indoc::indoc!(
+            "@prefix foaf: <http://xmlns.com/foaf/0.1/> .
+               @prefix : <http://example.org/> .
+             PREFIX wdt: <http://www.wikidata.org/prop/direct/>
+             @base <http://example.org/> .
+             ex:a ex:b ex:c .
+             @prefix broken <http://example.org/> .
+             "
+        );
+        assert_eq!(
+            parse_turtle_prefixes(text),
+            pairs(&[
+                ("foaf", "http://xmlns.com/foaf/0.1/"),
+                ("", "http://example.org/"),
+                ("wdt", "http://www.wikidata.org/prop/direct/"),
+            ])
+        );
+    }
+
+    #[test]
+    fn prefix_map() {
+        let value = json!({
+            "foaf": "http://xmlns.com/foaf/0.1/",
+            "dc": "http://purl.org/dc/elements/1.1/",
+            "broken": 42
+        });
+        assert_eq!(
+            parse_prefix_map(&value).unwrap(),
+            pairs(&[
+                ("dc", "http://purl.org/dc/elements/1.1/"),
+                ("foaf", "http://xmlns.com/foaf/0.1/"),
+            ])
+        );
+        assert!(parse_prefix_map(&json!(["foaf"])).is_err());
+    }
+
+    #[test]
+    fn jsonld_context() {
+        let context = json!([
+            "https://schema.org/",
+            {
+                "@vocab": "http://example.org/",
+                "foaf": "http://xmlns.com/foaf/0.1/",
+                "name": "http://xmlns.com/foaf/0.1/name",
+                "ex": {"@id": "http://example.org/vocab", "@prefix": true},
+                "knows": {"@id": "http://xmlns.com/foaf/0.1/knows", "@type": "@id"}
+            }
+        ]);
+        assert_eq!(
+            parse_jsonld_context(&context),
+            pairs(&[
+                ("ex", "http://example.org/vocab"),
+                ("foaf", "http://xmlns.com/foaf/0.1/"),
+            ])
+        );
+    }
+
+    #[test]
+    fn later_sources_override() {
+        let converter = create_converter(pairs(&[
+            ("ex", "http://example.org/"),
+            ("foaf", "http://xmlns.com/foaf/0.1/"),
+            ("ex", "http://example.com/"),
+            ("friend", "http://xmlns.com/foaf/0.1/"),
+        ]));
+        assert_eq!(converter.len(), 2);
+        assert_eq!(
+            converter.expand("ex:a").unwrap(),
+            "http://example.com/a".to_string()
+        );
+        assert_eq!(
+            converter
+                .compress("http://xmlns.com/foaf/0.1/knows")
+                .unwrap(),
+            "friend:knows".to_string()
+        );
+        assert!(converter.find_by_prefix("foaf").is_err());
+    }
+
+    #[test]
+    fn builtin_snapshot() {
+        let prefixes = builtin_prefixes(&BuiltinPrefixesSettings::default());
+        for prefix in [
+            "rdf", "rdfs", "owl", "xsd", "skos", "foaf", "dcterms", "schema", "wd", "wdt", "p",
+            "ps", "pq", "geo",
+        ] {
+            assert!(
+                prefixes.iter().any(|(other, _)| other == prefix),
+                "\"{}\" is missing in the snapshot",
+                prefix
+            );
+        }
+        // NOTE: A namespace that occurs twice would silently drop a prefix.
+        let converter = create_converter(prefixes.clone());
+        assert_eq!(converter.len(), prefixes.len());
+        assert_eq!(
+            converter
+                .compress("http://www.wikidata.org/entity/statement/Q42")
+                .unwrap(),
+            "wds:Q42"
+        );
+    }
+
+    #[test]
+    fn builtin_snapshot_disabled() {
+        let prefixes = builtin_prefixes(&BuiltinPrefixesSettings {
+            enabled: true,
+            disabled: vec!["wd".to_string(), "geo".to_string()],
+        });
+        assert!(!prefixes
+            .iter()
+            .any(|(prefix, _)| prefix == "wd" || prefix == "geo"));
+        assert!(prefixes.iter().any(|(prefix, _)| prefix == "wdt"));
+        assert!(builtin_prefixes(&BuiltinPrefixesSettings {
+            enabled: false,
+            disabled: vec![],
+        })
+        .is_empty());
+    }
+
+    #[test]
+    fn builtin_snapshot_override() {
+        let mut settings = Settings::default();
+        settings.prefixes.push(PrefixSetting {
+            prefix: "geo".to_string(),
+            namespace: "http://www.w3.org/2003/01/geo/wgs84_pos#".to_string(),
+        });
+        let converter = build_prefix_registry(&settings);
+        assert_eq!(
+            converter.expand("geo:lat").unwrap(),
+            "http://www.w3.org/2003/01/geo/wgs84_pos#lat"
+        );
+        assert!(converter.find_by_prefix("wgs").is_err());
+        assert!(converter.find_by_prefix("rdf").is_ok());
+    }
+}
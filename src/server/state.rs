@@ -1,10 +1,11 @@
 use std::collections::HashMap;
 
-use tree_sitter::Tree;
+use log::warn;
+use tree_sitter::{InputEdit, Parser, Tree};
 
 use super::lsp::{
     errors::{ErrorCode, ResponseError},
-    textdocument::{TextDocumentItem, TextEdit},
+    textdocument::{byte_index_to_point, TextDocumentItem, TextEdit},
     TextDocumentContentChangeEvent, TraceValue,
 };
 
@@ -19,6 +20,9 @@ pub struct ServerState {
     pub status: ServerStatus,
     pub trace_value: TraceValue,
     documents: HashMap<String, (TextDocumentItem, Option<Tree>)>,
+    /// Documents that changed since their diagnostics were last published,
+    /// with the time (see `now`) their diagnostics are due.
+    pending_diagnostics: HashMap<String, u64>,
 }
 
 impl ServerState {
@@ -27,6 +31,7 @@ impl ServerState {
             status: ServerStatus::Initializing,
             trace_value: TraceValue::Off,
             documents: HashMap::new(),
+            pending_diagnostics: HashMap::new(),
         }
     }
 
@@ -35,19 +40,99 @@ impl ServerState {
             .insert(text_document.uri.clone(), (text_document, tree));
     }
 
+    pub(super) fn remove_document(&mut self, uri: &str) -> Option<TextDocumentItem> {
+        self.pending_diagnostics.remove(uri);
+        self.documents.remove(uri).map(|(document, _tree)| document)
+    }
+
+    /// Marks the diagnostics of a document as due at `due`, a later change postpones them.
+    pub(super) fn mark_diagnostics_pending(&mut self, uri: &str, due: u64) {
+        self.pending_diagnostics.insert(uri.to_string(), due);
+    }
+
+    /// Removes and returns the documents whose diagnostics are due at `now`.
+    pub(super) fn take_due_diagnostics(&mut self, now: u64) -> Vec<String> {
+        let due: Vec<String> = self
+            .pending_diagnostics
+            .iter()
+            .filter(|(_uri, due)| **due <= now)
+            .map(|(uri, _due)| uri.clone())
+            .collect();
+        for uri in due.iter() {
+            self.pending_diagnostics.remove(uri);
+        }
+        due
+    }
+
+    /// The time the next pending diagnostics are due.
+    pub(super) fn next_diagnostics_due(&self) -> Option<u64> {
+        self.pending_diagnostics.values().min().copied()
+    }
+
+    /// Applies the content changes to the document and reparses it.
+    ///
+    /// Every change is also applied to the stored parse-tree (as a tree-sitter `InputEdit`),
+    /// this allows the parser to reuse the unchanged parts of the old tree.
+    /// Changes with a version that is not newer than the current version of the document
+    /// are stale and get rejected.
     pub(super) fn change_document(
         &mut self,
         uri: &String,
+        version: u32,
         content_changes: Vec<TextDocumentContentChangeEvent>,
-    ) -> Option<&TextDocumentItem> {
-        let document = &mut self.documents.get_mut(uri)?.0;
-        document.apply_text_edits(
-            content_changes
-                .into_iter()
-                .map(|change_event| TextEdit::from_text_document_content_change_event(change_event))
-                .collect::<Vec<TextEdit>>(),
-        );
-        return Some(document);
+        parser: &mut Parser,
+    ) -> Result<&TextDocumentItem, ResponseError> {
+        let (document, tree) = self
+            .documents
+            .get_mut(uri)
+            .ok_or_else(|| document_not_found(uri))?;
+        if version <= document.version() {
+            return Err(ResponseError::new(
+                ErrorCode::ContentModified,
+                &format!(
+                    "Received stale change for \"{}\" (version {}, current version {})",
+                    uri,
+                    version,
+                    document.version()
+                ),
+            ));
+        }
+        document.set_version(version);
+        for change_event in content_changes {
+            let text_edit = TextEdit::from_text_document_content_change_event(change_event);
+            let input_edit = text_edit.to_input_edit(&document.text);
+            let old_len = document.text.len();
+            document.apply_text_edit(text_edit);
+            match (tree.as_mut(), input_edit) {
+                (Some(tree), Some(input_edit)) => {
+                    tree.edit(&input_edit);
+                    // NOTE: The document appends a trailing newline, if the edit removed it.
+                    let edited_len = old_len + input_edit.new_end_byte - input_edit.old_end_byte;
+                    if edited_len < document.text.len() {
+                        let end_position = byte_index_to_point(&document.text, edited_len);
+                        tree.edit(&InputEdit {
+                            start_byte: edited_len,
+                            old_end_byte: edited_len,
+                            new_end_byte: document.text.len(),
+                            start_position: end_position,
+                            old_end_position: end_position,
+                            new_end_position: byte_index_to_point(
+                                &document.text,
+                                document.text.len(),
+                            ),
+                        });
+                    }
+                }
+                // NOTE: Without a valid edit the old tree can not be reused.
+                _ => *tree = None,
+            }
+        }
+        let new_tree = parser.parse(document.text.as_bytes(), tree.as_ref());
+        if new_tree.is_none() {
+            warn!("Could not build new parse-tree for \"{}\"", uri);
+        }
+        *tree = new_tree;
+        Ok(document)
     }
 
     pub(super) fn get_state(&self, uri: &str) -> Result<(&TextDocumentItem, &Tree), ResponseError> {
@@ -78,28 +163,171 @@ impl ServerState {
         Ok(&self
             .documents
             .get(uri)
-            .ok_or(ResponseError::new(
-                ErrorCode::InvalidRequest,
-                &format!("Requested document \"{}\"could not be found", uri),
-            ))?
+            .ok_or_else(|| document_not_found(uri))?
             .0)
     }
+}
 
-    pub(super) fn update_tree(
-        &mut self,
-        uri: &str,
-        new_tree: Option<Tree>,
-    ) -> Result<(), ResponseError> {
-        if self.documents.get(uri).is_some() {
-            self.documents
-                .entry(uri.to_string())
-                .and_modify(|(_document, old_tree)| *old_tree = new_tree);
-            Ok(())
-        } else {
-            Err(ResponseError::new(
-                ErrorCode::InternalError,
-                &format!("Could not update parse-tree, no entry for \"{}\"", uri),
-            ))
+/// The current time in milliseconds since the Unix epoch.
+#[cfg(target_arch = "wasm32")]
+pub(super) fn now() -> u64 {
+    web_sys::js_sys::Date::now() as u64
+}
+
+/// The current time in milliseconds since the Unix epoch.
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis() as u64)
+}
+
+fn document_not_found(uri: &str) -> ResponseError {
+    ResponseError::new(
+        ErrorCode::InvalidRequest,
+        &format!("Requested document \"{}\" could not be found", uri),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use tree_sitter::{Node, Parser};
+    use tree_sitter_sparql::LANGUAGE;
+
+    use crate::server::lsp::{
+        errors::ErrorCode,
+        textdocument::{Range, TextDocumentItem},
+        TextDocumentContentChangeEvent,
+    };
+
+    use super::ServerState;
+
+    fn change(range: Range, text: &str) -> TextDocumentContentChangeEvent {
+        TextDocumentContentChangeEvent {
+            range,
+            text: text.to_string(),
         }
     }
+
+    fn collect_nodes(node: Node) -> Vec<(&'static str, tree_sitter::Range)> {
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.children(&mut cursor).collect();
+        std::iter::once((node.kind(), node.range()))
+            .chain(children.into_iter().flat_map(collect_nodes))
+            .collect()
+    }
+
+    fn assert_incremental_parse(text: &str, changes: Vec<TextDocumentContentChangeEvent>) {
+        let mut parser = Parser::new();
+        parser.set_language(&LANGUAGE.into()).unwrap();
+        let mut state = ServerState::new();
+        let document = TextDocumentItem::new("uri", text);
+        let tree = parser.parse(&document.text, None);
+        state.add_document(document, tree);
+        state
+            .change_document(&"uri".to_string(), 1, changes, &mut parser)
+            .unwrap();
+        let (document, tree) = state.get_state("uri").unwrap();
+        let fresh_tree = parser.parse(&document.text, None).unwrap();
+        assert_eq!(
+            collect_nodes(tree.root_node()),
+            collect_nodes(fresh_tree.root_node())
+        );
+    }
+
+    #[test]
+    fn incremental_parse_single_line() {
+        assert_incremental_parse(
+            "SELECT * WHERE { ?s ?p ?o }\n",
+            vec![
+                change(Range::new(0, 25, 0, 25), " . ?o ?p ?x"),
+                change(Range::new(0, 7, 0, 8), "?s ?x"),
+            ],
+        );
+    }
+
+    #[test]
+    fn incremental_parse_multi_line() {
+        assert_incremental_parse(
+            "PREFIX ab: <ä😀>\nSELECT * WHERE {\n  ?s ?p \"😀\"\n}\n",
+            vec![
+                change(Range::new(2, 9, 2, 9), "x"),
+                change(Range::new(2, 11, 3, 0), " .\n  FILTER (?s)\n"),
+                change(Range::new(0, 0, 1, 0), ""),
+            ],
+        );
+    }
+
+    #[test]
+    fn incremental_parse_trailing_newline() {
+        assert_incremental_parse(
+            "SELECT * WHERE { ?s ?p ?o }\n",
+            vec![change(Range::new(0, 27, 1, 0), "")],
+        );
+    }
+
+    #[test]
+    fn change_tracks_version() {
+        let mut parser = Parser::new();
+        parser.set_language(&LANGUAGE.into()).unwrap();
+        let mut state = ServerState::new();
+        state.add_document(TextDocumentItem::new("uri", "SELECT * {}\n"), None);
+        let uri = "uri".to_string();
+        let document = state
+            .change_document(
+                &uri,
+                3,
+                vec![change(Range::new(0, 7, 0, 8), "?s")],
+                &mut parser,
+            )
+            .unwrap();
+        assert_eq!(document.version(), 3);
+        assert_eq!(document.text, "SELECT ?s {}\n");
+        assert!(state.get_tree("uri").is_ok());
+    }
+
+    #[test]
+    fn change_rejects_stale_version() {
+        let mut parser = Parser::new();
+        parser.set_language(&LANGUAGE.into()).unwrap();
+        let mut state = ServerState::new();
+        state.add_document(TextDocumentItem::new("uri", "SELECT * {}\n"), None);
+        let uri = "uri".to_string();
+        state
+            .change_document(
+                &uri,
+                2,
+                vec![change(Range::new(0, 7, 0, 8), "?s")],
+                &mut parser,
+            )
+            .unwrap();
+        for stale_version in [1, 2] {
+            let error = state
+                .change_document(
+                    &uri,
+                    stale_version,
+                    vec![change(Range::new(0, 7, 0, 9), "?o")],
+                    &mut parser,
+                )
+                .unwrap_err();
+            assert_eq!(error.code, ErrorCode::ContentModified);
+        }
+        let document = state.get_document("uri").unwrap();
+        assert_eq!(document.version(), 2);
+        assert_eq!(document.text, "SELECT ?s {}\n");
+    }
+
+    #[test]
+    fn remove_document() {
+        let mut parser = Parser::new();
+        parser.set_language(&LANGUAGE.into()).unwrap();
+        let mut state = ServerState::new();
+        let document = TextDocumentItem::new("uri", "SELECT * {}\n");
+        let tree = parser.parse(&document.text, None);
+        state.add_document(document, tree);
+        assert!(state.remove_document("uri").is_some());
+        assert!(state.get_document("uri").is_err());
+        assert!(state.get_tree("uri").is_err());
+        assert!(state.remove_document("uri").is_none());
+    }
 }
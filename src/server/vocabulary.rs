@@ -0,0 +1,482 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::Path,
+};
+
+use log::{error, info};
+use rio_api::{
+    model::{Literal, Subject, Term as RioTerm, Triple},
+    parser::TriplesParser,
+};
+use rio_turtle::{NTriplesParser, TurtleParser};
+use rio_xml::RdfXmlParser;
+
+use super::configuration::Settings;
+
+const RDF: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+const RDFS: &str = "http://www.w3.org/2000/01/rdf-schema#";
+const OWL: &str = "http://www.w3.org/2002/07/owl#";
+
+/// The local names of the `rdf:` and `rdfs:` predicates that describe a term.
+const INDEXED_PREDICATES: [&str; 7] = [
+    "type",
+    "label",
+    "comment",
+    "domain",
+    "range",
+    "subClassOf",
+    "subPropertyOf",
+];
+
+/// A literal with an optional language tag.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Text {
+    pub(crate) value: String,
+    pub(crate) language: Option<String>,
+}
+
+/// The object of a statement, as far as the vocabulary is concerned.
+#[derive(Debug, Clone, PartialEq)]
+enum Object {
+    Iri(String),
+    Literal(Text),
+    /// Blank nodes (e.g. OWL restrictions) are not indexed.
+    BlankNode,
+}
+
+/// Everything the vocabularies state about an IRI.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Term {
+    pub(crate) iri: String,
+    pub(crate) types: Vec<String>,
+    pub(crate) labels: Vec<Text>,
+    pub(crate) comments: Vec<Text>,
+    pub(crate) domains: Vec<String>,
+    pub(crate) ranges: Vec<String>,
+    pub(crate) super_classes: Vec<String>,
+    pub(crate) super_properties: Vec<String>,
+}
+
+/// Picks the English (or untagged) text if there is one, the first text otherwise.
+fn preferred_text(texts: &[Text]) -> Option<&str> {
+    texts
+        .iter()
+        .find(|text| {
+            text.language
+                .as_ref()
+                .is_none_or(|language| language.to_lowercase().starts_with("en"))
+        })
+        .or(texts.first())
+        .map(|text| text.value.as_str())
+}
+
+impl Term {
+    pub(crate) fn label(&self) -> Option<&str> {
+        preferred_text(&self.labels)
+    }
+
+    pub(crate) fn comment(&self) -> Option<&str> {
+        preferred_text(&self.comments)
+    }
+
+    pub(crate) fn is_class(&self) -> bool {
+        !self.super_classes.is_empty()
+            || self
+                .types
+                .iter()
+                .any(|class| *class == format!("{RDFS}Class") || *class == format!("{OWL}Class"))
+    }
+
+    pub(crate) fn is_property(&self) -> bool {
+        !self.domains.is_empty()
+            || !self.ranges.is_empty()
+            || !self.super_properties.is_empty()
+            || self.types.iter().any(|class| {
+                *class == format!("{RDF}Property")
+                    || [
+                        "ObjectProperty",
+                        "DatatypeProperty",
+                        "AnnotationProperty",
+                        "FunctionalProperty",
+                        "TransitiveProperty",
+                        "SymmetricProperty",
+                    ]
+                    .iter()
+                    .any(|kind| *class == format!("{OWL}{kind}"))
+            })
+    }
+}
+
+/// The part of an IRI up to the last `#` or `/`, e.g. `http://xmlns.com/foaf/0.1/`.
+pub(crate) fn namespace_of(iri: &str) -> &str {
+    match iri.rfind(['#', '/']) {
+        Some(index) => &iri[..=index],
+        None => iri,
+    }
+}
+
+/// The classes and properties of the local vocabulary files, indexed by IRI.
+#[derive(Debug, Default)]
+pub(crate) struct Vocabulary {
+    terms: HashMap<String, Term>,
+    /// The namespaces of all described IRIs.
+    namespaces: HashSet<String>,
+}
+
+impl Vocabulary {
+    pub(crate) fn get(&self, iri: &str) -> Option<&Term> {
+        self.terms.get(iri)
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub(crate) fn classes(&self) -> impl Iterator<Item = &Term> {
+        self.terms.values().filter(|term| term.is_class())
+    }
+
+    pub(crate) fn properties(&self) -> impl Iterator<Item = &Term> {
+        self.terms.values().filter(|term| term.is_property())
+    }
+
+    /// The classes `class` is a subclass of, directly or indirectly, including `class` itself.
+    pub(crate) fn super_classes_of<'a>(&'a self, class: &'a str) -> HashSet<&'a str> {
+        let mut classes = HashSet::from([class]);
+        let mut pending = vec![class];
+        while let Some(class) = pending.pop() {
+            for super_class in self
+                .get(class)
+                .into_iter()
+                .flat_map(|term| &term.super_classes)
+            {
+                if classes.insert(super_class) {
+                    pending.push(super_class);
+                }
+            }
+        }
+        classes
+    }
+
+    /// Checks if the vocabulary describes IRIs of the namespace of `iri`.
+    pub(crate) fn covers_namespace(&self, iri: &str) -> bool {
+        self.namespaces.contains(namespace_of(iri))
+    }
+
+    fn add_statement(&mut self, subject: &str, predicate: &str, object: Object) {
+        // NOTE: Other statements, e.g. about instances in a file that mixes schema and data,
+        // do not describe a term and must not mark the namespace of their subject as covered.
+        let Some(local_name) = predicate
+            .strip_prefix(RDF)
+            .or(predicate.strip_prefix(RDFS))
+            .filter(|local_name| INDEXED_PREDICATES.contains(local_name))
+        else {
+            return;
+        };
+        if !self.terms.contains_key(subject) {
+            self.namespaces.insert(namespace_of(subject).to_string());
+        }
+        let term = self
+            .terms
+            .entry(subject.to_string())
+            .or_insert_with(|| Term {
+                iri: subject.to_string(),
+                ..Default::default()
+            });
+        match (local_name, object) {
+            ("type", Object::Iri(iri)) => term.types.push(iri),
+            ("label", Object::Literal(text)) => term.labels.push(text),
+            ("comment", Object::Literal(text)) => term.comments.push(text),
+            ("domain", Object::Iri(iri)) => term.domains.push(iri),
+            ("range", Object::Iri(iri)) => term.ranges.push(iri),
+            ("subClassOf", Object::Iri(iri)) => term.super_classes.push(iri),
+            ("subPropertyOf", Object::Iri(iri)) => term.super_properties.push(iri),
+            _ => {}
+        }
+    }
+
+    fn add_triple(&mut self, triple: Triple) -> bool {
+        let Subject::NamedNode(subject) = triple.subject else {
+            return false;
+        };
+        let object = match triple.object {
+            RioTerm::NamedNode(node) => Object::Iri(node.iri.to_string()),
+            RioTerm::Literal(Literal::Simple { value })
+            | RioTerm::Literal(Literal::Typed { value, .. }) => Object::Literal(Text {
+                value: value.to_string(),
+                language: None,
+            }),
+            RioTerm::Literal(Literal::LanguageTaggedString { value, language }) => {
+                Object::Literal(Text {
+                    value: value.to_string(),
+                    language: Some(language.to_string()),
+                })
+            }
+            RioTerm::BlankNode(_) | RioTerm::Triple(_) => Object::BlankNode,
+        };
+        self.add_statement(subject.iri, triple.predicate.iri, object);
+        true
+    }
+
+    /// Adds the statements of a Turtle, N-Triples or RDF/XML document, returns their number.
+    fn add_triples<P: TriplesParser>(&mut self, mut parser: P) -> Result<usize, String> {
+        let mut count = 0;
+        parser
+            .parse_all(&mut |triple| -> Result<(), P::Error> {
+                count += self.add_triple(triple) as usize;
+                Ok(())
+            })
+            .map_err(|error| error.to_string())?;
+        Ok(count)
+    }
+
+    /// Adds the statements of a Turtle document, returns their number.
+    pub(crate) fn add_turtle(&mut self, text: &str) -> Result<usize, String> {
+        self.add_triples(TurtleParser::new(text.as_bytes(), None))
+    }
+
+    /// Loads a vocabulary file, the format is chosen by the file extension.
+    ///
+    /// Statements read before a syntax error are kept.
+    fn load_file(&mut self, path: &Path) -> Result<usize, String> {
+        let content = fs::read_to_string(path).map_err(|error| error.to_string())?;
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("ttl") | Some("turtle") => self.add_turtle(&content),
+            Some("nt") => self.add_triples(NTriplesParser::new(content.as_bytes())),
+            Some("rdf") | Some("owl") | Some("xml") => {
+                self.add_triples(RdfXmlParser::new(content.as_bytes(), None))
+            }
+            _ => Err(
+                "Unknown vocabulary format, expected Turtle (.ttl), N-Triples (.nt) or RDF/XML (.rdf, .owl)"
+                    .to_string(),
+            ),
+        }
+    }
+}
+
+/// Builds the vocabulary from the files listed in `vocabulary_files`.
+///
+/// Files that can not be read are skipped.
+pub(super) fn load_vocabulary(settings: &Settings) -> Vocabulary {
+    let mut vocabulary = Vocabulary::default();
+    for path in settings.vocabulary_files.iter() {
+        match vocabulary.load_file(Path::new(path)) {
+            Ok(count) => info!("Loaded {} statements from \"{}\"", count, path),
+            Err(message) => error!("Could not load vocabulary from \"{}\":\n{}", path, message),
+        }
+    }
+    vocabulary
+}
+
+#[cfg(test)]
+mod tests {
+    use rio_turtle::NTriplesParser;
+    use rio_xml::RdfXmlParser;
+
+    use super::{namespace_of, Vocabulary};
+
+    const TURTLE: &str = indoc::indoc!(
+        r#"
+        @prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .
+        @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+        @prefix owl: <http://www.w3.org/2002/07/owl#> .
+        @prefix ex: <http://example.org/vocab#> .
+
+        ex:Person a owl:Class ;
+            rdfs:label "Person"@en, "Person"@de ;
+            rdfs:comment "A human being."@en .
+        ex:Employee rdfs:subClassOf ex:Person, [ a owl:Restriction ] .
+        ex:knows a owl:ObjectProperty ;
+            rdfs:label "kennt"@de, "knows" ;
+            rdfs:domain ex:Person ;
+            rdfs:range ex:Person .
+        ex:name rdfs:domain ex:Person .
+        [] rdfs:label "anonymous" .
+        "#
+    );
+
+    fn turtle_vocabulary() -> Vocabulary {
+        let mut vocabulary = Vocabulary::default();
+        assert_eq!(vocabulary.add_turtle(TURTLE), Ok(12));
+        vocabulary
+    }
+
+    #[test]
+    fn turtle() {
+        let vocabulary = turtle_vocabulary();
+        assert_eq!(vocabulary.len(), 4);
+        let person = vocabulary.get("http://example.org/vocab#Person").unwrap();
+        assert!(person.is_class() && !person.is_property());
+        assert_eq!(person.label(), Some("Person"));
+        assert_eq!(person.comment(), Some("A human being."));
+        let knows = vocabulary.get("http://example.org/vocab#knows").unwrap();
+        assert!(knows.is_property() && !knows.is_class());
+        assert_eq!(knows.label(), Some("knows"));
+        assert_eq!(knows.domains, vec!["http://example.org/vocab#Person"]);
+        assert_eq!(knows.ranges, vec!["http://example.org/vocab#Person"]);
+        assert_eq!(
+            vocabulary
+                .get("http://example.org/vocab#Employee")
+                .unwrap()
+                .super_classes,
+            vec!["http://example.org/vocab#Person"]
+        );
+        let mut properties: Vec<&str> = vocabulary
+            .properties()
+            .map(|property| property.iri.as_str())
+            .collect();
+        properties.sort();
+        assert_eq!(
+            properties,
+            vec![
+                "http://example.org/vocab#knows",
+                "http://example.org/vocab#name"
+            ]
+        );
+        assert_eq!(vocabulary.classes().count(), 2);
+    }
+
+    #[test]
+    fn super_classes() {
+        let mut vocabulary = Vocabulary::default();
+        vocabulary
+            .add_turtle(indoc::indoc!(
+                "
+                @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+                <http://example.org/a> rdfs:subClassOf <http://example.org/b> .
+                <http://example.org/b> rdfs:subClassOf <http://example.org/c>, <http://example.org/a> .
+                "
+            ))
+            .unwrap();
+        let mut classes: Vec<&str> = vocabulary
+            .super_classes_of("http://example.org/a")
+            .into_iter()
+            .collect();
+        classes.sort();
+        assert_eq!(
+            classes,
+            vec![
+                "http://example.org/a",
+                "http://example.org/b",
+                "http://example.org/c"
+            ]
+        );
+    }
+
+    #[test]
+    fn ntriples() {
+        let mut vocabulary = Vocabulary::default();
+        let text = indoc::indoc!(
+            r#"
+            <http://example.org/a> <http://www.w3.org/2000/01/rdf-schema#label> "A" .
+            <http://example.org/a> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://www.w3.org/1999/02/22-rdf-syntax-ns#Property> .
+            "#
+        );
+        assert_eq!(
+            vocabulary.add_triples(NTriplesParser::new(text.as_bytes())),
+            Ok(2)
+        );
+        let term = vocabulary.get("http://example.org/a").unwrap();
+        assert_eq!(term.label(), Some("A"));
+        assert_eq!(
+            term.types,
+            vec!["http://www.w3.org/1999/02/22-rdf-syntax-ns#Property"]
+        );
+    }
+
+    #[test]
+    fn rdf_xml() {
+        let mut vocabulary = Vocabulary::default();
+        let text = indoc::indoc!(
+            r##"<?xml version="1.0"?>
+            <!DOCTYPE rdf:RDF [
+                <!ENTITY owl "http://www.w3.org/2002/07/owl#" >
+            ]>
+            <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+                     xmlns:rdfs="http://www.w3.org/2000/01/rdf-schema#"
+                     xmlns:owl="http://www.w3.org/2002/07/owl#"
+                     xml:base="http://example.org/vocab"
+                     xml:lang="en">
+                <owl:Class rdf:about="#Person" rdfs:label="Person">
+                    <rdfs:comment>A human being.</rdfs:comment>
+                </owl:Class>
+                <rdf:Description rdf:ID="knows">
+                    <rdf:type rdf:resource="&owl;ObjectProperty"/>
+                    <rdfs:label xml:lang="de">kennt</rdfs:label>
+                    <rdfs:domain rdf:resource="#Person"/>
+                    <rdfs:range>
+                        <owl:Class rdf:about="http://example.org/other#Agent"/>
+                    </rdfs:range>
+                </rdf:Description>
+                <owl:Class rdf:about="#Employee">
+                    <rdfs:subClassOf>
+                        <owl:Restriction>
+                            <owl:onProperty rdf:resource="#knows"/>
+                        </owl:Restriction>
+                    </rdfs:subClassOf>
+                </owl:Class>
+            </rdf:RDF>"##
+        );
+        // NOTE: Statements about blank nodes (the restriction) are dropped.
+        assert_eq!(
+            vocabulary.add_triples(RdfXmlParser::new(text.as_bytes(), None)),
+            Ok(10)
+        );
+        let person = vocabulary.get("http://example.org/vocab#Person").unwrap();
+        assert!(person.is_class());
+        assert_eq!(person.labels[0].language.as_deref(), Some("en"));
+        assert_eq!(person.label(), Some("Person"));
+        assert_eq!(person.comment(), Some("A human being."));
+        let knows = vocabulary.get("http://example.org/vocab#knows").unwrap();
+        assert!(knows.is_property());
+        assert_eq!(knows.label(), Some("kennt"));
+        assert_eq!(knows.domains, vec!["http://example.org/vocab#Person"]);
+        assert_eq!(knows.ranges, vec!["http://example.org/other#Agent"]);
+        assert!(vocabulary
+            .get("http://example.org/other#Agent")
+            .unwrap()
+            .is_class());
+        let employee = vocabulary.get("http://example.org/vocab#Employee").unwrap();
+        assert!(employee.is_class() && employee.super_classes.is_empty());
+    }
+
+    #[test]
+    fn invalid_rdf_xml() {
+        let mut vocabulary = Vocabulary::default();
+        assert!(vocabulary
+            .add_triples(RdfXmlParser::new("<rdf:RDF>".as_bytes(), None))
+            .is_err());
+    }
+
+    #[test]
+    fn syntax_errors_keep_previous_statements() {
+        let mut vocabulary = Vocabulary::default();
+        let text = "<http://example.org/a> <http://www.w3.org/2000/01/rdf-schema#label> \"A\" .\n<http://example.org/b> broken";
+        assert!(vocabulary.add_turtle(text).is_err());
+        assert!(vocabulary.get("http://example.org/a").is_some());
+    }
+
+    #[test]
+    fn namespaces() {
+        let mut vocabulary = turtle_vocabulary();
+        assert_eq!(
+            namespace_of("http://xmlns.com/foaf/0.1/knows"),
+            "http://xmlns.com/foaf/0.1/"
+        );
+        assert!(vocabulary.covers_namespace("http://example.org/vocab#unknown"));
+        assert!(!vocabulary.covers_namespace("http://example.org/other#Person"));
+        // NOTE: Data does not describe its subjects, their namespace is not covered.
+        vocabulary
+            .add_turtle("<http://www.wikidata.org/entity/Q42> <http://www.wikidata.org/prop/direct/P31> <http://www.wikidata.org/entity/Q5> .")
+            .unwrap();
+        assert!(vocabulary
+            .get("http://www.wikidata.org/entity/Q42")
+            .is_none());
+        assert!(!vocabulary.covers_namespace("http://www.wikidata.org/entity/Q1"));
+    }
+}
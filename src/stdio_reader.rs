@@ -2,19 +2,62 @@ use core::panic;
 use std::{
     io::{self, BufReader, Read},
     process::exit,
+    sync::mpsc::{channel, RecvTimeoutError, Sender},
+    thread,
+    time::Duration,
 };
 
 use log::error;
 
-pub fn listen_stdio(mut message_handler: impl FnMut(String) -> ()) {
+pub enum StdioEvent {
+    /// A complete message was received.
+    Message(String),
+    /// The timeout requested by the handler of the last event passed without a new message.
+    Timeout,
+}
+
+fn next_byte(reader: &mut impl Read) -> Option<io::Result<u8>> {
+    let mut byte = [0u8];
+    match reader.read(&mut byte) {
+        Ok(0) => None,
+        Ok(_) => Some(Ok(byte[0])),
+        Err(error) => Some(Err(error)),
+    }
+}
+
+/// Passes every message received on stdin to the event handler.
+///
+/// The handler returns how long to wait for the next message, before it gets called
+/// with `StdioEvent::Timeout` instead.
+pub fn listen_stdio(mut event_handler: impl FnMut(StdioEvent) -> Option<Duration>) {
+    let (sender, receiver) = channel();
+    // NOTE: Reading blocks, so it happens on its own thread to be able to time out.
+    thread::spawn(move || read_messages(sender));
+    let mut timeout = None;
+    loop {
+        let event = match timeout {
+            Some(timeout) => match receiver.recv_timeout(timeout) {
+                Ok(message) => StdioEvent::Message(message),
+                Err(RecvTimeoutError::Timeout) => StdioEvent::Timeout,
+                Err(RecvTimeoutError::Disconnected) => break,
+            },
+            None => match receiver.recv() {
+                Ok(message) => StdioEvent::Message(message),
+                Err(_) => break,
+            },
+        };
+        timeout = (event_handler)(event);
+    }
+}
+
+fn read_messages(sender: Sender<String>) {
     let stdin = io::stdin();
-    let reader = BufReader::new(stdin);
+    let mut reader = BufReader::new(stdin);
 
-    let mut bytes = reader.bytes();
     let mut buffer = vec![];
 
     loop {
-        match bytes.next() {
+        match next_byte(&mut reader) {
             Some(Ok(byte)) => {
                 buffer.push(byte);
             }
@@ -36,7 +79,7 @@ pub fn listen_stdio(mut message_handler: impl FnMut(String) -> ()) {
             let content_length: u32 = cl_string.parse().expect("Failed to parse Content-Length");
             buffer.clear();
             for ele in 0..content_length {
-                match bytes.next() {
+                match next_byte(&mut reader) {
                     Some(Ok(byte)) => {
                         buffer.push(byte);
                     }
@@ -58,7 +101,12 @@ pub fn listen_stdio(mut message_handler: impl FnMut(String) -> ()) {
                     }
                 }
             }
-            (message_handler)(String::from_utf8(buffer.clone()).unwrap());
+            if sender
+                .send(String::from_utf8(buffer.clone()).unwrap())
+                .is_err()
+            {
+                return;
+            }
             buffer.clear();
         }
     }